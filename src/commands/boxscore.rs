@@ -1,6 +1,53 @@
-use nhl_api::{Client, GameId, Boxscore};
+use std::cmp::Ordering;
+use nhl_api::{Client, GameId, Boxscore, SkaterStats};
+use super::{paint, Highlight};
+use super::formatting::{format_decimal, format_signed, format_toi, labeled_rule, parse_toi, per_sixty, NumberLocale, SeparatorStyle};
 
-pub fn format_boxscore(boxscore: &Boxscore) -> String {
+/// Widest separator rules used in the boxscore; narrower terminals get shorter rules
+const HEADER_RULE_WIDTH: usize = 60;
+const SECTION_RULE_WIDTH: usize = 80;
+
+/// Width of a skater row without the optional columns (#, name, position, G, A, P, +/-, TOI)
+const SKATER_BASE_WIDTH: usize = 53;
+/// Width added by the FO% column
+const FACEOFF_COLUMN_WIDTH: usize = 6;
+/// Width added by the P/60 and S/60 columns
+const RATE_COLUMNS_WIDTH: usize = 14;
+
+/// Optional skater columns that fit the output width; the rates go first, then FO%
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SkaterColumns {
+    faceoffs: bool,
+    rates: bool,
+}
+
+impl SkaterColumns {
+    fn fit(width: usize, rates: bool) -> Self {
+        let faceoffs = width >= SKATER_BASE_WIDTH + FACEOFF_COLUMN_WIDTH;
+        SkaterColumns {
+            faceoffs,
+            rates: rates && faceoffs && width >= SKATER_BASE_WIDTH + FACEOFF_COLUMN_WIDTH + RATE_COLUMNS_WIDTH,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn format_boxscore(boxscore: &Boxscore, width: usize, quiet: bool, locale: NumberLocale, rates: bool, color: bool, style: SeparatorStyle) -> String {
+    let mut output = String::new();
+    let header_rule = style.underline().repeat(HEADER_RULE_WIDTH.min(width));
+    let section_width = SECTION_RULE_WIDTH.min(width);
+
+    if !quiet {
+        output.push_str(&format_game_header(boxscore, &header_rule));
+    }
+    let columns = SkaterColumns::fit(width, rates);
+    output.push_str(&format_team_stats(boxscore, section_width, locale, columns, color, style));
+
+    output
+}
+
+/// Game banner: matchup, date, venue, status and clock
+fn format_game_header(boxscore: &Boxscore, header_rule: &str) -> String {
     let mut output = String::new();
 
     // Display game header
@@ -8,7 +55,7 @@ pub fn format_boxscore(boxscore: &Boxscore) -> String {
         boxscore.away_team.common_name.default,
         boxscore.home_team.common_name.default
     ));
-    output.push_str(&format!("{}\n", "═".repeat(60)));
+    output.push_str(&format!("{}\n", header_rule));
     output.push_str(&format!("Date: {} | Venue: {}\n",
         boxscore.game_date,
         boxscore.venue.default
@@ -21,9 +68,95 @@ pub fn format_boxscore(boxscore: &Boxscore) -> String {
         output.push_str(&format!("Time: {}\n", boxscore.clock.time_remaining));
     }
 
+    output
+}
+
+/// Normalize a TOI string to MM:SS, leaving values the API sent in another shape untouched
+fn display_toi(toi: &str) -> String {
+    parse_toi(toi).map(format_toi).unwrap_or_else(|| toi.to_string())
+}
+
+/// Header cells for the optional per-60 rate columns
+fn rate_headers(rates: bool) -> String {
+    if rates {
+        format!(" {:>6} {:>6}", "P/60", "S/60")
+    } else {
+        String::new()
+    }
+}
+
+/// Faceoff win percentage with one decimal, or a dash for skaters who took no faceoffs
+/// (the API reports those as 0)
+fn display_faceoff_pct(pctg: f64, locale: NumberLocale) -> String {
+    if pctg > 0.0 {
+        format_decimal(pctg * 100.0, 1, locale)
+    } else {
+        "—".to_string()
+    }
+}
+
+/// Per-60 point and shot rates for a skater, empty unless rates are enabled
+fn rate_columns(rates: bool, points: i32, shots: i32, toi: &str, locale: NumberLocale) -> String {
+    if !rates {
+        return String::new();
+    }
+    let toi_seconds = parse_toi(toi).unwrap_or(0);
+    format!(" {:>6} {:>6}",
+        format_decimal(per_sixty(points, toi_seconds), 2, locale),
+        format_decimal(per_sixty(shots, toi_seconds), 2, locale)
+    )
+}
+
+/// Signed plus/minus padded to its column, green when positive and red when negative
+fn display_plus_minus(value: i32, color: bool) -> String {
+    let text = format!("{:>4}", format_signed(value));
+    match value.cmp(&0) {
+        Ordering::Greater => paint(&text, Highlight::Positive, color),
+        Ordering::Less => paint(&text, Highlight::Negative, color),
+        Ordering::Equal => text,
+    }
+}
+
+/// Labeled rule, column headers and one row per skater
+#[allow(clippy::too_many_arguments)]
+fn format_skater_section(title: &str, players: &[SkaterStats], section_width: usize, locale: NumberLocale, columns: SkaterColumns, color: bool, style: SeparatorStyle) -> String {
+    let mut output = String::new();
+
+    output.push_str(&format!("\n{}\n", labeled_rule(title, section_width, style)));
+    let faceoff_header = if columns.faceoffs { format!(" {:>5}", "FO%") } else { String::new() };
+    output.push_str(&format!("{:<3} {:<20} {:<4} {:>3} {:>3} {:>3} {:>4} {:>6}{}{}\n",
+        "#", "Name", "Pos", "G", "A", "P", "+/-", "TOI", faceoff_header, rate_headers(columns.rates)
+    ));
+    for player in players {
+        let faceoff_pct = if columns.faceoffs {
+            format!(" {:>5}", display_faceoff_pct(player.faceoff_winning_pctg, locale))
+        } else {
+            String::new()
+        };
+        output.push_str(&format!("{:<3} {:<20} {:<4} {:>3} {:>3} {:>3} {} {:>6}{}{}\n",
+            player.sweater_number,
+            player.name.default,
+            player.position,
+            player.goals,
+            player.assists,
+            player.points,
+            display_plus_minus(player.plus_minus, color),
+            display_toi(&player.toi),
+            faceoff_pct,
+            rate_columns(columns.rates, player.points, player.sog, &player.toi, locale)
+        ));
+    }
+
+    output
+}
+
+/// Score, shots and player stats tables for both teams
+fn format_team_stats(boxscore: &Boxscore, section_width: usize, locale: NumberLocale, columns: SkaterColumns, color: bool, style: SeparatorStyle) -> String {
+    let mut output = String::new();
+
     // Display score
     output.push_str(&format!("\n{:<20} {:>3}\n", "Team", "Score"));
-    output.push_str(&format!("{}\n", "─".repeat(25)));
+    output.push_str(&format!("{}\n", style.rule().repeat(25)));
     output.push_str(&format!("{:<20} {:>3}\n",
         boxscore.away_team.abbrev,
         boxscore.away_team.score
@@ -35,7 +168,7 @@ pub fn format_boxscore(boxscore: &Boxscore) -> String {
 
     // Display shots on goal
     output.push_str(&format!("\n{:<20} {:>3}\n", "Team", "SOG"));
-    output.push_str(&format!("{}\n", "─".repeat(25)));
+    output.push_str(&format!("{}\n", style.rule().repeat(25)));
     output.push_str(&format!("{:<20} {:>3}\n",
         boxscore.away_team.abbrev,
         boxscore.away_team.sog
@@ -46,50 +179,25 @@ pub fn format_boxscore(boxscore: &Boxscore) -> String {
     ));
 
     // Display player stats - Away Team
-    output.push_str(&format!("\n{} - Forwards\n", boxscore.away_team.abbrev));
-    output.push_str(&format!("{}\n", "─".repeat(80)));
-    output.push_str(&format!("{:<3} {:<20} {:<4} {:>3} {:>3} {:>3} {:>4} {:>6}\n",
-        "#", "Name", "Pos", "G", "A", "P", "+/-", "TOI"
+    output.push_str(&format_skater_section(
+        &format!("{} Forwards", boxscore.away_team.abbrev),
+        &boxscore.player_by_game_stats.away_team.forwards,
+        section_width, locale, columns, color, style
     ));
-    for player in &boxscore.player_by_game_stats.away_team.forwards {
-        output.push_str(&format!("{:<3} {:<20} {:<4} {:>3} {:>3} {:>3} {:>4} {:>6}\n",
-            player.sweater_number,
-            player.name.default,
-            player.position,
-            player.goals,
-            player.assists,
-            player.points,
-            player.plus_minus,
-            player.toi
-        ));
-    }
 
-    output.push_str(&format!("\n{} - Defense\n", boxscore.away_team.abbrev));
-    output.push_str(&format!("{}\n", "─".repeat(80)));
-    output.push_str(&format!("{:<3} {:<20} {:<4} {:>3} {:>3} {:>3} {:>4} {:>6}\n",
-        "#", "Name", "Pos", "G", "A", "P", "+/-", "TOI"
+    output.push_str(&format_skater_section(
+        &format!("{} Defense", boxscore.away_team.abbrev),
+        &boxscore.player_by_game_stats.away_team.defense,
+        section_width, locale, columns, color, style
     ));
-    for player in &boxscore.player_by_game_stats.away_team.defense {
-        output.push_str(&format!("{:<3} {:<20} {:<4} {:>3} {:>3} {:>3} {:>4} {:>6}\n",
-            player.sweater_number,
-            player.name.default,
-            player.position,
-            player.goals,
-            player.assists,
-            player.points,
-            player.plus_minus,
-            player.toi
-        ));
-    }
 
-    output.push_str(&format!("\n{} - Goalies\n", boxscore.away_team.abbrev));
-    output.push_str(&format!("{}\n", "─".repeat(80)));
+    output.push_str(&format!("\n{}\n", labeled_rule(&format!("{} Goalies", boxscore.away_team.abbrev), section_width, style)));
     output.push_str(&format!("{:<3} {:<20} {:>4} {:>6} {:>6} {:>6}\n",
         "#", "Name", "SA", "Saves", "GA", "SV%"
     ));
     for goalie in &boxscore.player_by_game_stats.away_team.goalies {
         let sv_pct = goalie.save_pctg
-            .map(|p| format!("{:.3}", p))
+            .map(|p| format_decimal(p, 3, locale))
             .unwrap_or_else(|| "-".to_string());
         output.push_str(&format!("{:<3} {:<20} {:>4} {:>6} {:>6} {:>6}\n",
             goalie.sweater_number,
@@ -102,50 +210,25 @@ pub fn format_boxscore(boxscore: &Boxscore) -> String {
     }
 
     // Display player stats - Home Team
-    output.push_str(&format!("\n{} - Forwards\n", boxscore.home_team.abbrev));
-    output.push_str(&format!("{}\n", "─".repeat(80)));
-    output.push_str(&format!("{:<3} {:<20} {:<4} {:>3} {:>3} {:>3} {:>4} {:>6}\n",
-        "#", "Name", "Pos", "G", "A", "P", "+/-", "TOI"
+    output.push_str(&format_skater_section(
+        &format!("{} Forwards", boxscore.home_team.abbrev),
+        &boxscore.player_by_game_stats.home_team.forwards,
+        section_width, locale, columns, color, style
     ));
-    for player in &boxscore.player_by_game_stats.home_team.forwards {
-        output.push_str(&format!("{:<3} {:<20} {:<4} {:>3} {:>3} {:>3} {:>4} {:>6}\n",
-            player.sweater_number,
-            player.name.default,
-            player.position,
-            player.goals,
-            player.assists,
-            player.points,
-            player.plus_minus,
-            player.toi
-        ));
-    }
 
-    output.push_str(&format!("\n{} - Defense\n", boxscore.home_team.abbrev));
-    output.push_str(&format!("{}\n", "─".repeat(80)));
-    output.push_str(&format!("{:<3} {:<20} {:<4} {:>3} {:>3} {:>3} {:>4} {:>6}\n",
-        "#", "Name", "Pos", "G", "A", "P", "+/-", "TOI"
+    output.push_str(&format_skater_section(
+        &format!("{} Defense", boxscore.home_team.abbrev),
+        &boxscore.player_by_game_stats.home_team.defense,
+        section_width, locale, columns, color, style
     ));
-    for player in &boxscore.player_by_game_stats.home_team.defense {
-        output.push_str(&format!("{:<3} {:<20} {:<4} {:>3} {:>3} {:>3} {:>4} {:>6}\n",
-            player.sweater_number,
-            player.name.default,
-            player.position,
-            player.goals,
-            player.assists,
-            player.points,
-            player.plus_minus,
-            player.toi
-        ));
-    }
 
-    output.push_str(&format!("\n{} - Goalies\n", boxscore.home_team.abbrev));
-    output.push_str(&format!("{}\n", "─".repeat(80)));
+    output.push_str(&format!("\n{}\n", labeled_rule(&format!("{} Goalies", boxscore.home_team.abbrev), section_width, style)));
     output.push_str(&format!("{:<3} {:<20} {:>4} {:>6} {:>6} {:>6}\n",
         "#", "Name", "SA", "Saves", "GA", "SV%"
     ));
     for goalie in &boxscore.player_by_game_stats.home_team.goalies {
         let sv_pct = goalie.save_pctg
-            .map(|p| format!("{:.3}", p))
+            .map(|p| format_decimal(p, 3, locale))
             .unwrap_or_else(|| "-".to_string());
         output.push_str(&format!("{:<3} {:<20} {:>4} {:>6} {:>6} {:>6}\n",
             goalie.sweater_number,
@@ -160,8 +243,83 @@ pub fn format_boxscore(boxscore: &Boxscore) -> String {
     output
 }
 
-pub async fn run(client: &Client, game_id: i64) {
+#[allow(clippy::too_many_arguments)]
+pub async fn run(client: &Client, game_id: i64, quiet: bool, locale: NumberLocale, rates: bool, color: bool, style: SeparatorStyle) {
     let game_id = GameId::new(game_id);
-    let boxscore = client.boxscore(&game_id).await.unwrap();
-    print!("{}", format_boxscore(&boxscore));
+    let boxscore = client.boxscore(&game_id).await
+        .unwrap_or_else(|e| super::exit_with_error(&format!("Failed to fetch boxscore: {}", e)));
+    print!("{}", format_boxscore(&boxscore, super::terminal_width(), quiet, locale, rates, color, style));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_width::UnicodeWidthStr;
+
+    #[test]
+    fn zero_faceoffs_show_a_dash() {
+        assert_eq!(display_faceoff_pct(0.0, NumberLocale::EnUs), "—");
+        assert_eq!(display_faceoff_pct(0.525, NumberLocale::EnUs), "52.5");
+        assert_eq!(display_faceoff_pct(0.525, NumberLocale::DeDe), "52,5");
+    }
+
+    #[test]
+    fn plus_minus_is_colored_by_sign() {
+        assert_eq!(display_plus_minus(3, false), "  +3");
+        assert_eq!(display_plus_minus(-2, false), "  -2");
+        assert_eq!(display_plus_minus(0, true), "   0");
+        assert_eq!(display_plus_minus(3, true), paint("  +3", Highlight::Positive, true));
+        assert_eq!(display_plus_minus(-2, true), paint("  -2", Highlight::Negative, true));
+        assert_ne!(display_plus_minus(3, true), "  +3");
+    }
+
+    fn skater(name: &str) -> SkaterStats {
+        serde_json::from_value(serde_json::json!({
+            "playerId": 1,
+            "sweaterNumber": 63,
+            "name": { "default": name },
+            "position": "C",
+            "goals": 1,
+            "assists": 2,
+            "points": 3,
+            "plusMinus": 2,
+            "pim": 0,
+            "hits": 1,
+            "powerPlayGoals": 0,
+            "sog": 4,
+            "faceoffWinningPctg": 0.5,
+            "toi": "18:00",
+            "blockedShots": 0,
+            "shifts": 22,
+            "giveaways": 0,
+            "takeaways": 1,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn narrow_widths_drop_the_optional_columns() {
+        let players = [skater("B. Marchand")];
+        let section = |width: usize| {
+            let columns = SkaterColumns::fit(width, true);
+            format_skater_section("BOS Forwards", &players, width, NumberLocale::EnUs, columns, false, SeparatorStyle::Default)
+        };
+
+        let wide = section(80);
+        assert!(wide.contains("FO%") && wide.contains("P/60") && wide.contains("S/60"));
+        assert!(wide.contains(" 50.0 "));
+        assert!(wide.lines().all(|line| line.width() <= 80));
+
+        let medium = section(60);
+        assert!(medium.contains("FO%") && !medium.contains("P/60"));
+        assert!(medium.lines().all(|line| line.width() <= 60));
+
+        let narrow = section(55);
+        assert!(!narrow.contains("FO%") && !narrow.contains("P/60"));
+        assert!(narrow.contains("B. Marchand"));
+        assert!(narrow.lines().all(|line| line.width() <= 55));
+
+        // Rates stay off when they weren't asked for
+        assert_eq!(SkaterColumns::fit(200, false), SkaterColumns { faceoffs: true, rates: false });
+    }
 }
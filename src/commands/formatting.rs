@@ -0,0 +1,290 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Decimal and digit-grouping conventions used when rendering numbers
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum NumberLocale {
+    #[default]
+    EnUs,
+    EnCa,
+    DeDe,
+    FrFr,
+    FrCa,
+    SvSe,
+    FiFi,
+}
+
+impl NumberLocale {
+    /// Look up a locale by name ("en-US", "de_DE", ...), case-insensitively
+    pub fn parse(name: &str) -> Option<NumberLocale> {
+        match name.trim().replace('_', "-").to_lowercase().as_str() {
+            "en-us" => Some(NumberLocale::EnUs),
+            "en-ca" => Some(NumberLocale::EnCa),
+            "de-de" => Some(NumberLocale::DeDe),
+            "fr-fr" => Some(NumberLocale::FrFr),
+            "fr-ca" => Some(NumberLocale::FrCa),
+            "sv-se" => Some(NumberLocale::SvSe),
+            "fi-fi" => Some(NumberLocale::FiFi),
+            _ => None,
+        }
+    }
+
+    /// (decimal separator, grouping separator)
+    fn separators(&self) -> (char, char) {
+        match self {
+            NumberLocale::EnUs | NumberLocale::EnCa => ('.', ','),
+            NumberLocale::DeDe => (',', '.'),
+            NumberLocale::FrFr | NumberLocale::FrCa | NumberLocale::SvSe | NumberLocale::FiFi => (',', ' '),
+        }
+    }
+}
+
+/// Insert the grouping separator every three digits of an unsigned digit string
+fn group_digits(digits: &str, separator: char) -> String {
+    let mut output = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            output.push(separator);
+        }
+        output.push(digit);
+    }
+    output
+}
+
+/// Format a number with a fixed count of decimals, e.g. 1234.5 -> "1,234.5" or "1.234,5"
+pub fn format_decimal(value: f64, decimals: usize, locale: NumberLocale) -> String {
+    let (decimal, grouping) = locale.separators();
+    let formatted = format!("{:.*}", decimals, value.abs());
+    let (whole, fraction) = match formatted.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (formatted.as_str(), None),
+    };
+
+    let mut output = String::new();
+    // Don't print "-0.0" for values that round to zero
+    if value < 0.0 && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        output.push('-');
+    }
+    output.push_str(&group_digits(whole, grouping));
+    if let Some(fraction) = fraction {
+        output.push(decimal);
+        output.push_str(fraction);
+    }
+    output
+}
+
+/// Parse a time-on-ice string ("MM:SS", minutes may exceed 59) into seconds
+pub fn parse_toi(toi: &str) -> Option<u32> {
+    let (minutes, seconds) = toi.trim().split_once(':')?;
+    let minutes: u32 = minutes.parse().ok()?;
+    let seconds: u32 = seconds.parse().ok()?;
+    if seconds >= 60 {
+        return None;
+    }
+    Some(minutes * 60 + seconds)
+}
+
+/// Format seconds of ice time as "MM:SS", keeping minutes past the hour (e.g. "62:05")
+pub fn format_toi(seconds: u32) -> String {
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Rate of a counting stat per 60 minutes of ice time; 0 when there is no ice time
+pub fn per_sixty(count: i32, toi_seconds: u32) -> f64 {
+    if toi_seconds == 0 {
+        return 0.0;
+    }
+    count as f64 * 3600.0 / toi_seconds as f64
+}
+
+/// Format a differential with an explicit sign: "+5", "-3", "0"
+pub fn format_signed(value: i32) -> String {
+    if value > 0 {
+        format!("+{}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Glyphs used for table rules and heading underlines
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SeparatorStyle {
+    /// Light rules (─) with double heading underlines (═)
+    #[default]
+    Default,
+    Single,
+    Double,
+    Dashed,
+    Ascii,
+}
+
+impl SeparatorStyle {
+    /// Look up a style by name, case-insensitively
+    pub fn parse(name: &str) -> Option<SeparatorStyle> {
+        match name.trim().to_lowercase().as_str() {
+            "default" => Some(SeparatorStyle::Default),
+            "single" => Some(SeparatorStyle::Single),
+            "double" => Some(SeparatorStyle::Double),
+            "dashed" => Some(SeparatorStyle::Dashed),
+            "ascii" => Some(SeparatorStyle::Ascii),
+            _ => None,
+        }
+    }
+
+    /// Glyph for rules under table headers and around sections
+    pub fn rule(&self) -> &'static str {
+        match self {
+            SeparatorStyle::Default | SeparatorStyle::Single => "─",
+            SeparatorStyle::Double => "═",
+            SeparatorStyle::Dashed => "┄",
+            SeparatorStyle::Ascii => "-",
+        }
+    }
+
+    /// Glyph for heading underlines
+    pub fn underline(&self) -> &'static str {
+        match self {
+            SeparatorStyle::Default => "═",
+            other => other.rule(),
+        }
+    }
+
+    /// The style to draw with: ASCII, whatever the configured style, when the
+    /// terminal can't show box-drawing characters
+    pub fn for_terminal(self, unicode: bool) -> SeparatorStyle {
+        if unicode { self } else { SeparatorStyle::Ascii }
+    }
+}
+
+/// A horizontal rule of `width` columns with the label centered in it: "──── BOS Forwards ────"
+pub fn labeled_rule(label: &str, width: usize, style: SeparatorStyle) -> String {
+    let label = format!(" {} ", label);
+    let label_width = label.width();
+    if label_width >= width {
+        return label.trim().to_string();
+    }
+    let left = (width - label_width) / 2;
+    let right = width - label_width - left;
+    format!("{}{}{}", style.rule().repeat(left), label, style.rule().repeat(right))
+}
+
+/// Cut `text` to at most `width` display columns, ending with `ellipsis` when it had to be cut.
+/// Works on characters, never splitting a multi-byte or wide character.
+pub fn truncate_display(text: &str, width: usize, ellipsis: &str) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+
+    // Too narrow for the ellipsis: keep as much of the text as fits
+    let ellipsis = if ellipsis.width() < width { ellipsis } else { "" };
+    let available = width - ellipsis.width();
+
+    let mut output = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if used + char_width > available {
+            break;
+        }
+        output.push(c);
+        used += char_width;
+    }
+    // "Montréal…" rather than "Montréal …"
+    output.truncate(output.trim_end().len());
+    output.push_str(ellipsis);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_style_renders_its_own_glyphs() {
+        let cases = [
+            (SeparatorStyle::Default, "─", "═"),
+            (SeparatorStyle::Single, "─", "─"),
+            (SeparatorStyle::Double, "═", "═"),
+            (SeparatorStyle::Dashed, "┄", "┄"),
+            (SeparatorStyle::Ascii, "-", "-"),
+        ];
+        for (style, rule, underline) in cases {
+            assert_eq!(style.rule(), rule);
+            assert_eq!(style.underline(), underline);
+            assert_eq!(labeled_rule("BOS", 11, style), format!("{} BOS {}", rule.repeat(3), rule.repeat(3)));
+        }
+    }
+
+    #[test]
+    fn style_names_parse_case_insensitively() {
+        assert_eq!(SeparatorStyle::parse(" Double "), Some(SeparatorStyle::Double));
+        assert_eq!(SeparatorStyle::parse("ASCII"), Some(SeparatorStyle::Ascii));
+        assert_eq!(SeparatorStyle::parse("heavy"), None);
+    }
+
+    #[test]
+    fn ascii_fallback_ignores_the_style() {
+        for style in [SeparatorStyle::Default, SeparatorStyle::Single, SeparatorStyle::Double, SeparatorStyle::Dashed] {
+            assert_eq!(style.for_terminal(true), style);
+            let fallback = style.for_terminal(false);
+            assert_eq!(fallback, SeparatorStyle::Ascii);
+            assert_eq!(labeled_rule("BOS", 11, fallback), "--- BOS ---");
+        }
+    }
+
+    #[test]
+    fn toi_round_trips() {
+        for toi in ["00:00", "05:07", "19:59", "62:05"] {
+            assert_eq!(parse_toi(toi).map(format_toi).as_deref(), Some(toi));
+        }
+        // Past the hour stays in minutes
+        assert_eq!(parse_toi("62:05"), Some(3725));
+        assert_eq!(format_toi(3725), "62:05");
+        assert_eq!(parse_toi("10:60"), None);
+        assert_eq!(parse_toi("1005"), None);
+    }
+
+    #[test]
+    fn per_sixty_without_ice_time_is_zero() {
+        assert_eq!(per_sixty(3, 0), 0.0);
+        assert_eq!(per_sixty(0, 0), 0.0);
+        assert_eq!(per_sixty(2, 1800), 4.0);
+    }
+
+    #[test]
+    fn truncates_to_display_width() {
+        assert_eq!(truncate_display("Boston", 10, "…"), "Boston");
+        assert_eq!(truncate_display("Boston", 6, "…"), "Boston");
+        assert_eq!(truncate_display("Montréal Canadiens", 10, "…"), "Montréal…");
+        assert_eq!(truncate_display("Montréal Canadiens", 11, "…"), "Montréal C…");
+        // Wide characters are never split
+        assert_eq!(truncate_display("日本語テキスト", 6, "…"), "日本…");
+        // Too narrow for the ellipsis
+        assert_eq!(truncate_display("Boston", 1, "…"), "B");
+        assert_eq!(truncate_display("Boston", 0, "…"), "");
+    }
+
+    #[test]
+    fn decimals_follow_the_locale() {
+        assert_eq!(format_decimal(1234.5, 1, NumberLocale::EnUs), "1,234.5");
+        assert_eq!(format_decimal(1234.5, 1, NumberLocale::DeDe), "1.234,5");
+        assert_eq!(format_decimal(1234.5, 1, NumberLocale::FrFr), "1 234,5");
+        assert_eq!(format_decimal(-0.04, 1, NumberLocale::EnUs), "0.0");
+        assert_eq!(format_decimal(-1234.0, 0, NumberLocale::EnUs), "-1,234");
+    }
+
+    #[test]
+    fn digits_group_by_three() {
+        assert_eq!(group_digits("0", ','), "0");
+        assert_eq!(group_digits("999", ','), "999");
+        assert_eq!(group_digits("1000", ','), "1,000");
+        assert_eq!(group_digits("999999", ','), "999,999");
+        assert_eq!(group_digits("1000000", ','), "1,000,000");
+    }
+
+    #[test]
+    fn signed_values_show_their_sign() {
+        assert_eq!(format_signed(3), "+3");
+        assert_eq!(format_signed(0), "0");
+        assert_eq!(format_signed(-2), "-2");
+    }
+}
@@ -1,5 +1,344 @@
+pub mod formatting;
 pub mod standings;
 pub mod boxscore;
 pub mod schedule;
 pub mod scores;
 pub mod scores_format;
+#[cfg(test)]
+pub mod test_support;
+
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use crossterm::style::{Attribute, Color, Stylize};
+use nhl_api::{DailySchedule, GameDate, ScheduleGame, Standing};
+use std::io::IsTerminal;
+
+/// Print an error for a bad command-line value and exit
+pub fn exit_with_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1);
+}
+
+/// Season id (e.g. 20242025) that a date belongs to; seasons roll over on July 1st
+pub fn season_for_date(date: NaiveDate) -> i64 {
+    let start_year = if date.month() >= 7 { date.year() } else { date.year() - 1 } as i64;
+    start_year * 10000 + start_year + 1
+}
+
+/// Season id for today's local date
+pub fn current_season() -> i64 {
+    season_for_date(Local::now().date_naive())
+}
+
+/// Check that a season id is an 8-digit NHL season made of two consecutive years (e.g. 20242025),
+/// suggesting the likely intended id when it isn't
+pub fn validate_season(season: i64) -> Result<i64, String> {
+    let error = |hint: String| {
+        format!("Invalid season '{}'. Use an 8-digit season id like {}{}", season, current_season(), hint)
+    };
+
+    // A bare year: assume the season starting that year
+    if (1000..=9999).contains(&season) {
+        return Err(error(format!(" (did you mean {}{}?)", season, season + 1)));
+    }
+    if !(10000000..=99999999).contains(&season) {
+        return Err(error(String::new()));
+    }
+
+    let first_year = season / 10000;
+    let second_year = season % 10000;
+    if second_year == first_year + 1 {
+        Ok(season)
+    } else if first_year == second_year + 1 {
+        Err(error(format!(" (years are reversed, did you mean {}{}?)", second_year, first_year)))
+    } else {
+        Err(error(format!(" (the second year must follow the first, e.g. {}{})", first_year, first_year + 1)))
+    }
+}
+
+/// Calendar date of a GameDate, resolving "now" to today's local date
+pub fn naive_date(game_date: &GameDate) -> NaiveDate {
+    match game_date {
+        GameDate::Date(date) => *date,
+        GameDate::Now => Local::now().date_naive(),
+    }
+}
+
+/// Parse a date argument: YYYY-MM-DD, "today", "yesterday", "tomorrow",
+/// or a signed day offset from today such as "+1" or "-2"
+pub fn parse_game_date(input: &str) -> Result<GameDate, String> {
+    let input = input.trim();
+    let today = Local::now().date_naive();
+
+    let offset_days = match input.to_lowercase().as_str() {
+        "today" => return Ok(GameDate::today()),
+        "yesterday" => -1,
+        "tomorrow" => 1,
+        other if other.starts_with('+') || other.starts_with('-') => other
+            .parse::<i64>()
+            .map_err(|_| format!("Invalid day offset '{}'. Use e.g. +1 or -2", input))?,
+        _ => {
+            return NaiveDate::parse_from_str(input, "%Y-%m-%d")
+                .map(GameDate::Date)
+                .map_err(|_| format!(
+                    "Invalid date '{}'. Use YYYY-MM-DD, today, yesterday, tomorrow, or an offset like +1/-2",
+                    input
+                ));
+        }
+    };
+
+    Duration::try_days(offset_days)
+        .and_then(|offset| today.checked_add_signed(offset))
+        .map(GameDate::Date)
+        .ok_or_else(|| format!("Day offset '{}' is out of range", input))
+}
+
+/// Abbreviations of the current NHL teams
+pub const TEAM_ABBREVS: [&str; 32] = [
+    "ANA", "BOS", "BUF", "CAR", "CBJ", "CGY", "CHI", "COL",
+    "DAL", "DET", "EDM", "FLA", "LAK", "MIN", "MTL", "NJD",
+    "NSH", "NYI", "NYR", "OTT", "PHI", "PIT", "SEA", "SJS",
+    "STL", "TBL", "TOR", "UTA", "VAN", "VGK", "WPG", "WSH",
+];
+
+/// Validate a team abbreviation (case-insensitive), returning it uppercased
+pub fn validate_team_abbrev(abbrev: &str) -> Result<String, String> {
+    let upper = abbrev.trim().to_uppercase();
+    if TEAM_ABBREVS.contains(&upper.as_str()) {
+        Ok(upper)
+    } else {
+        Err(format!(
+            "Unknown team abbreviation '{}'. Valid teams: {}",
+            abbrev,
+            TEAM_ABBREVS.join(", ")
+        ))
+    }
+}
+
+/// Whether a game involves the given team, home or away
+pub fn game_involves_team(game: &ScheduleGame, abbrev: &str) -> bool {
+    game.away_team.abbrev.eq_ignore_ascii_case(abbrev) || game.home_team.abbrev.eq_ignore_ascii_case(abbrev)
+}
+
+/// Keep only the games involving `team`, if a team filter is given
+pub fn filter_schedule_by_team(schedule: &mut DailySchedule, team: Option<&str>) {
+    if let Some(abbrev) = team {
+        schedule.games.retain(|game| game_involves_team(game, abbrev));
+    }
+}
+
+/// Line printed for a date without games, naming the team when a --team filter is given
+pub fn no_games_message(team: Option<&str>) -> String {
+    match team {
+        Some(abbrev) => format!("No games for {} on this date.", abbrev),
+        None => "No games scheduled for this date.".to_string(),
+    }
+}
+
+/// Keep only the games involving at least one of `teams`; an empty list keeps every game
+pub fn filter_schedule_by_teams(schedule: &mut DailySchedule, teams: &[String]) {
+    if teams.is_empty() {
+        return;
+    }
+    schedule.games.retain(|game| teams.iter().any(|abbrev| game_involves_team(game, abbrev)));
+}
+
+/// Keep only the standings rows of the given teams; an empty list keeps every row
+pub fn filter_standings_by_teams(standings: &[Standing], teams: &[String]) -> Vec<Standing> {
+    if teams.is_empty() {
+        return standings.to_vec();
+    }
+    standings
+        .iter()
+        .filter(|standing| teams.iter().any(|abbrev| standing.team_abbrev.default.eq_ignore_ascii_case(abbrev)))
+        .cloned()
+        .collect()
+}
+
+/// Whether CLI output should be colored: disabled by --no-color, by a non-empty
+/// NO_COLOR environment variable, or when stdout isn't a terminal (piped output).
+/// A non-empty FORCE_COLOR keeps color on piped output, but not over the other two.
+pub fn should_use_color(no_color_flag: bool, no_color_env: bool, force_color_env: bool, stdout_is_tty: bool) -> bool {
+    !no_color_flag && !no_color_env && (force_color_env || stdout_is_tty)
+}
+
+/// Color decision for the current process
+pub fn use_color(no_color_flag: bool) -> bool {
+    let env_set = |name: &str| std::env::var_os(name).is_some_and(|value| !value.is_empty());
+    should_use_color(no_color_flag, env_set("NO_COLOR"), env_set("FORCE_COLOR"), std::io::stdout().is_terminal())
+}
+
+/// Width assumed when stdout isn't a terminal (wide enough for the two-column layouts)
+pub const DEFAULT_TERMINAL_WIDTH: usize = 120;
+
+/// Width available for CLI output: the terminal width, or the default when piped
+pub fn terminal_width() -> usize {
+    if !std::io::stdout().is_terminal() {
+        return DEFAULT_TERMINAL_WIDTH;
+    }
+    match crossterm::terminal::size() {
+        Ok((width, _)) if width > 0 => width as usize,
+        _ => DEFAULT_TERMINAL_WIDTH,
+    }
+}
+
+/// Whether the locale's character set is UTF-8, read from LC_ALL, LC_CTYPE then LANG.
+/// With none of them set, UTF-8 is assumed.
+pub fn locale_is_utf8() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()));
+    match locale {
+        Some(locale) => {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        None => true,
+    }
+}
+
+/// Style for highlighted CLI text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Highlight {
+    /// Section headers
+    Header,
+    /// Games in progress
+    Live,
+    /// Finished games and other de-emphasized text
+    Muted,
+    /// Favourable differentials
+    Positive,
+    /// Unfavourable differentials
+    Negative,
+}
+
+/// Apply a highlight to already padded text, or return it unchanged when color is off
+pub fn paint(text: &str, highlight: Highlight, color: bool) -> String {
+    if !color {
+        return text.to_string();
+    }
+    match highlight {
+        Highlight::Header => text.attribute(Attribute::Bold).to_string(),
+        Highlight::Live => text.with(Color::Green).attribute(Attribute::Bold).to_string(),
+        Highlight::Muted => text.with(Color::DarkGrey).to_string(),
+        Highlight::Positive => text.with(Color::Green).to_string(),
+        Highlight::Negative => text.with(Color::Red).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dates_keywords_and_offsets() {
+        let today = Local::now().date_naive();
+        let date = |input: &str| naive_date(&parse_game_date(input).unwrap());
+
+        assert_eq!(date("2024-11-02"), NaiveDate::from_ymd_opt(2024, 11, 2).unwrap());
+        assert_eq!(date(" today "), today);
+        assert_eq!(date("Yesterday"), today - Duration::days(1));
+        assert_eq!(date("tomorrow"), today + Duration::days(1));
+        assert_eq!(date("+3"), today + Duration::days(3));
+        assert_eq!(date("-2"), today - Duration::days(2));
+    }
+
+    #[test]
+    fn rejects_malformed_dates() {
+        assert!(parse_game_date("2024-13-01").is_err());
+        assert!(parse_game_date("11/02/2024").is_err());
+        assert!(parse_game_date("+x").is_err());
+        assert!(parse_game_date("+99999999999").is_err());
+    }
+
+    #[test]
+    fn validates_seasons() {
+        assert_eq!(validate_season(20242025), Ok(20242025));
+        let reversed = validate_season(20252024).unwrap_err();
+        assert!(reversed.contains("did you mean 20242025"), "{}", reversed);
+        let bare_year = validate_season(2024).unwrap_err();
+        assert!(bare_year.contains("did you mean 20242025"), "{}", bare_year);
+        assert!(validate_season(20242026).is_err());
+        assert!(validate_season(202425).is_err());
+    }
+
+    #[test]
+    fn seasons_roll_over_on_july_first() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        assert_eq!(season_for_date(date(2025, 6, 30)), 20242025);
+        assert_eq!(season_for_date(date(2025, 7, 1)), 20252026);
+        assert_eq!(season_for_date(date(2025, 1, 15)), 20242025);
+        assert_eq!(season_for_date(date(2024, 12, 31)), 20242025);
+    }
+
+    #[test]
+    fn games_involve_their_home_and_away_teams() {
+        let game = test_support::game(1, "BOS", "TOR", nhl_api::GameState::Future);
+        assert!(game_involves_team(&game, "BOS"));
+        assert!(game_involves_team(&game, "tor"));
+        assert!(!game_involves_team(&game, "MTL"));
+    }
+
+    #[test]
+    fn team_filter_keeps_home_and_away_games() {
+        let games = vec![
+            test_support::game(1, "BOS", "TOR", nhl_api::GameState::Future),
+            test_support::game(2, "MTL", "BOS", nhl_api::GameState::Future),
+            test_support::game(3, "NYR", "PIT", nhl_api::GameState::Future),
+        ];
+        let mut schedule = test_support::schedule(games.clone());
+        filter_schedule_by_team(&mut schedule, Some("BOS"));
+        assert_eq!(schedule.games.iter().map(|game| game.id).collect::<Vec<_>>(), vec![1, 2]);
+
+        let mut schedule = test_support::schedule(games.clone());
+        filter_schedule_by_team(&mut schedule, Some("SEA"));
+        assert!(schedule.games.is_empty());
+
+        let mut schedule = test_support::schedule(games);
+        filter_schedule_by_team(&mut schedule, None);
+        assert_eq!(schedule.games.len(), 3);
+    }
+
+    #[test]
+    fn empty_schedule_message_names_the_filtered_team() {
+        assert_eq!(no_games_message(Some("BOS")), "No games for BOS on this date.");
+        assert_eq!(no_games_message(None), "No games scheduled for this date.");
+    }
+
+    #[test]
+    fn color_decision() {
+        assert!(should_use_color(false, false, false, true));
+        assert!(!should_use_color(true, false, false, true));
+        assert!(!should_use_color(false, true, false, true));
+        assert!(!should_use_color(false, false, false, false));
+        assert!(should_use_color(false, false, true, false));
+        assert!(!should_use_color(true, false, true, false));
+        assert!(!should_use_color(false, true, true, true));
+    }
+
+    #[test]
+    fn followed_teams_filter_games_and_standings() {
+        let followed = vec!["BOS".to_string(), "edm".to_string()];
+        let games = vec![
+            test_support::game(1, "BOS", "TOR", nhl_api::GameState::Future),
+            test_support::game(2, "MTL", "OTT", nhl_api::GameState::Future),
+            test_support::game(3, "CGY", "EDM", nhl_api::GameState::Future),
+        ];
+
+        let mut schedule = test_support::schedule(games.clone());
+        filter_schedule_by_teams(&mut schedule, &followed);
+        assert_eq!(schedule.games.iter().map(|game| game.id).collect::<Vec<_>>(), vec![1, 3]);
+
+        let mut schedule = test_support::schedule(games);
+        filter_schedule_by_teams(&mut schedule, &[]);
+        assert_eq!(schedule.games.len(), 3);
+
+        let standings = vec![
+            test_support::standing("BOS", Some("Eastern"), "Atlantic", 10, 5, 1),
+            test_support::standing("TOR", Some("Eastern"), "Atlantic", 9, 6, 1),
+            test_support::standing("EDM", Some("Western"), "Pacific", 8, 7, 1),
+        ];
+        let abbrevs = |rows: Vec<Standing>| rows.iter().map(|row| row.team_abbrev.default.clone()).collect::<Vec<_>>();
+        assert_eq!(abbrevs(filter_standings_by_teams(&standings, &followed)), vec!["BOS", "EDM"]);
+        assert_eq!(abbrevs(filter_standings_by_teams(&standings, &[])), vec!["BOS", "TOR", "EDM"]);
+    }
+}
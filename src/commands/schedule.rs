@@ -1,6 +1,55 @@
-use nhl_api::{Client, GameDate, DailySchedule};
-use chrono::NaiveDate;
+use nhl_api::{Client, GameDate, DailySchedule, GameState};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::fmt;
+use super::{paint, Highlight};
 
+/// Game counts by state for a day's schedule
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScheduleSummary {
+    pub total: usize,
+    pub final_games: usize,
+    pub live: usize,
+    pub upcoming: usize,
+    pub postponed: usize,
+}
+
+impl fmt::Display for ScheduleSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let noun = if self.total == 1 { "game" } else { "games" };
+        write!(
+            f,
+            "{} {}, {} final, {} live, {} upcoming",
+            self.total, noun, self.final_games, self.live, self.upcoming
+        )?;
+        if self.postponed > 0 {
+            write!(f, ", {} postponed", self.postponed)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn summarize_schedule(schedule: &DailySchedule) -> ScheduleSummary {
+    let mut summary = ScheduleSummary {
+        total: schedule.games.len(),
+        ..ScheduleSummary::default()
+    };
+
+    for game in &schedule.games {
+        if matches!(game.game_state, GameState::Postponed) {
+            summary.postponed += 1;
+        } else if game.game_state.is_final() {
+            summary.final_games += 1;
+        } else if game.game_state.has_started() {
+            summary.live += 1;
+        } else {
+            summary.upcoming += 1;
+        }
+    }
+
+    summary
+}
+
+#[allow(dead_code)] // Library API; the binary prints day by day
 pub fn format_schedule(schedule: &DailySchedule) -> String {
     let mut output = String::new();
 
@@ -14,7 +63,7 @@ pub fn format_schedule(schedule: &DailySchedule) -> String {
         // Display each game in a box
         for (i, game) in schedule.games.iter().enumerate() {
             if i > 0 {
-                output.push_str("\n");
+                output.push('\n');
             }
 
             // Game box header
@@ -49,47 +98,91 @@ pub fn format_schedule(schedule: &DailySchedule) -> String {
     output
 }
 
-pub async fn run(client: &Client, date: Option<String>) {
-    let game_date = if let Some(date_str) = date {
-        // Parse date string
-        let parsed_date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
-            .expect("Invalid date format. Use YYYY-MM-DD");
-        GameDate::Date(parsed_date)
-    } else {
-        // Use today's date
-        GameDate::today()
-    };
+/// Monday-to-Sunday bounds of the ISO week containing `date`
+pub fn week_bounds(date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+    (monday, monday + Duration::days(6))
+}
 
-    let schedule = client.daily_schedule(Some(&game_date)).await.unwrap();
+/// Parse an ISO week like "2025-W07" into the Monday that starts it
+pub fn parse_iso_week(input: &str) -> Result<NaiveDate, String> {
+    let error = || format!("Invalid week '{}'. Use YYYY-Www, e.g. 2025-W07", input);
 
+    let (year, week) = input.trim().split_once("-W").ok_or_else(error)?;
+    let year: i32 = year.parse().map_err(|_| error())?;
+    let week: u32 = week.parse().map_err(|_| error())?;
+
+    NaiveDate::from_isoywd_opt(year, week, Weekday::Mon).ok_or_else(error)
+}
+
+/// Print the games of one day's schedule, with header and summary unless quiet
+fn print_games(schedule: &DailySchedule, team: Option<&str>, color: bool, quiet: bool) {
     // Display schedule header
-    println!("\nNHL Schedule - {}", schedule.date);
-    println!("{}", "=".repeat(80));
+    if !quiet {
+        println!("\n{}", paint(&format!("NHL Schedule - {}", schedule.date), Highlight::Header, color));
+        println!("{}", "=".repeat(80));
+    }
 
-    if schedule.number_of_games == 0 {
-        println!("No games scheduled for this date.");
-    } else {
-        println!("Games: {}\n", schedule.number_of_games);
+    if schedule.games.is_empty() {
+        if !quiet {
+            println!("{}", super::no_games_message(team));
+        }
+        return;
+    }
 
-        // Display each game
-        for game in &schedule.games {
-            println!("Game ID: {}", game.id);
-            println!("  {} @ {}",
-                game.away_team.abbrev,
-                game.home_team.abbrev
-            );
-            println!("  Time: {} (UTC)", game.start_time_utc);
-            println!("  Status: {}", game.game_state);
+    if !quiet {
+        println!("Games: {}\n", schedule.games.len());
+    }
 
-            // Display scores if available
-            if let (Some(away_score), Some(home_score)) = (game.away_team.score, game.home_team.score) {
-                println!("  Score: {} - {}", away_score, home_score);
-            }
-            println!();
+    // Display each game
+    for game in &schedule.games {
+        println!("Game ID: {}", game.id);
+        println!("  {} @ {}",
+            game.away_team.abbrev,
+            game.home_team.abbrev
+        );
+        println!("  Time: {} (UTC)", game.start_time_utc);
+        let status = format!("{}", game.game_state);
+        let status = if game.game_state.is_final() {
+            paint(&status, Highlight::Muted, color)
+        } else if game.game_state.has_started() {
+            paint(&status, Highlight::Live, color)
+        } else {
+            status
+        };
+        println!("  Status: {}", status);
+
+        // Display scores if available
+        if let (Some(away_score), Some(home_score)) = (game.away_team.score, game.home_team.score) {
+            println!("  Score: {} - {}", away_score, home_score);
         }
+        println!();
     }
 
+    if !quiet {
+        println!("{}\n", summarize_schedule(schedule));
+    }
+}
+
+pub async fn run(client: &Client, date: Option<String>, team: Option<String>, color: bool, quiet: bool) {
+    let game_date = if let Some(date_str) = date {
+        // Parse date string
+        super::parse_game_date(&date_str).unwrap_or_else(|e| super::exit_with_error(&e))
+    } else {
+        // Use today's date
+        GameDate::today()
+    };
+
+    let mut schedule = client.daily_schedule(Some(game_date.clone())).await
+        .unwrap_or_else(|e| super::exit_with_error(&format!("Failed to fetch schedule: {}", e)));
+    super::filter_schedule_by_team(&mut schedule, team.as_deref());
+
+    print_games(&schedule, team.as_deref(), color, quiet);
+
     // Display navigation info
+    if quiet {
+        return;
+    }
     if let Some(prev) = schedule.previous_start_date {
         println!("Previous date with games: {}", prev);
     }
@@ -97,3 +190,85 @@ pub async fn run(client: &Client, date: Option<String>) {
         println!("Next date with games: {}", next);
     }
 }
+
+/// Display the schedule for every day of a week.
+/// `week` is an ISO week (YYYY-Www); without one, the week containing `date` (or today) is used.
+pub async fn run_week(
+    client: &Client,
+    week: Option<String>,
+    date: Option<String>,
+    team: Option<String>,
+    color: bool,
+    quiet: bool,
+) {
+    let monday = match week {
+        Some(week) => parse_iso_week(&week).unwrap_or_else(|e| super::exit_with_error(&e)),
+        None => {
+            let game_date = match date {
+                Some(date_str) => super::parse_game_date(&date_str).unwrap_or_else(|e| super::exit_with_error(&e)),
+                None => GameDate::today(),
+            };
+            week_bounds(super::naive_date(&game_date)).0
+        }
+    };
+
+    let (_, sunday) = week_bounds(monday);
+    if !quiet {
+        println!("\n{}", paint(&format!("NHL Week - {} to {}", monday, sunday), Highlight::Header, color));
+    }
+
+    for day in monday.iter_days().take(7) {
+        let mut schedule = client.daily_schedule(Some(GameDate::Date(day))).await
+            .unwrap_or_else(|e| super::exit_with_error(&format!("Failed to fetch schedule for {}: {}", day, e)));
+        super::filter_schedule_by_team(&mut schedule, team.as_deref());
+        print_games(&schedule, team.as_deref(), color, quiet);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn week_bounds_cross_month_and_year_boundaries() {
+        assert_eq!(week_bounds(date(2024, 10, 31)), (date(2024, 10, 28), date(2024, 11, 3)));
+        assert_eq!(week_bounds(date(2025, 1, 1)), (date(2024, 12, 30), date(2025, 1, 5)));
+        // A Monday starts its own week
+        assert_eq!(week_bounds(date(2024, 12, 30)), (date(2024, 12, 30), date(2025, 1, 5)));
+    }
+
+    #[test]
+    fn iso_weeks_start_on_their_monday() {
+        assert_eq!(parse_iso_week("2025-W07"), Ok(date(2025, 2, 10)));
+        // Week 1 can start in the previous year
+        assert_eq!(parse_iso_week("2025-W01"), Ok(date(2024, 12, 30)));
+        assert_eq!(parse_iso_week("2020-W53"), Ok(date(2020, 12, 28)));
+        assert!(parse_iso_week("2021-W53").is_err());
+        assert!(parse_iso_week("2025-07").is_err());
+    }
+
+    #[test]
+    fn summary_counts_games_by_state() {
+        use crate::commands::test_support::{game, schedule};
+
+        let games = vec![
+            game(1, "BOS", "TOR", GameState::Final),
+            game(2, "MTL", "OTT", GameState::Off),
+            game(3, "NYR", "NJD", GameState::Live),
+            game(4, "EDM", "CGY", GameState::Critical),
+            game(5, "VAN", "SEA", GameState::Future),
+            game(6, "DAL", "STL", GameState::PreGame),
+            game(7, "LAK", "ANA", GameState::Postponed),
+        ];
+        let summary = summarize_schedule(&schedule(games));
+        assert_eq!(summary, ScheduleSummary { total: 7, final_games: 2, live: 2, upcoming: 2, postponed: 1 });
+        assert_eq!(summary.to_string(), "7 games, 2 final, 2 live, 2 upcoming, 1 postponed");
+
+        let summary = summarize_schedule(&schedule(vec![game(1, "BOS", "TOR", GameState::Future)]));
+        assert_eq!(summary.to_string(), "1 game, 0 final, 0 live, 1 upcoming");
+    }
+}
@@ -1,25 +1,29 @@
-use nhl_api::{Client, GameDate, GameId, Boxscore, GameClock, PeriodDescriptor};
-use chrono::NaiveDate;
+use nhl_api::{Client, GameDate, GameId, Boxscore, GameClock};
+use super::{paint, Highlight};
 
-pub async fn run(client: &Client, date: Option<String>) {
+pub async fn run(client: &Client, date: Option<String>, team: Option<String>, color: bool, quiet: bool) -> Result<(), String> {
     let game_date = if let Some(date_str) = date {
-        let parsed_date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
-            .expect("Invalid date format. Use YYYY-MM-DD");
-        GameDate::Date(parsed_date)
+        super::parse_game_date(&date_str)?
     } else {
         GameDate::today()
     };
 
-    let schedule = client.daily_schedule(Some(&game_date)).await.unwrap();
+    let mut schedule = client.daily_schedule(Some(game_date.clone())).await
+        .map_err(|e| format!("Failed to fetch schedule: {}", e))?;
+    super::filter_schedule_by_team(&mut schedule, team.as_deref());
 
     // Display header
-    println!("\n{}", "═".repeat(90));
-    println!("NHL SCORES - {}", schedule.date);
-    println!("{}\n", "═".repeat(90));
+    if !quiet {
+        println!("\n{}", "═".repeat(90));
+        println!("{}", paint(&format!("NHL SCORES - {}", schedule.date), Highlight::Header, color));
+        println!("{}\n", "═".repeat(90));
+    }
 
-    if schedule.number_of_games == 0 {
-        println!("No games scheduled for this date.\n");
-        return;
+    if schedule.games.is_empty() {
+        if !quiet {
+            println!("{}\n", super::no_games_message(team.as_deref()));
+        }
+        return Ok(());
     }
 
     // Process each game
@@ -36,23 +40,24 @@ pub async fn run(client: &Client, date: Option<String>) {
             let game_id = GameId::new(game.id);
             match client.boxscore(&game_id).await {
                 Ok(boxscore) => {
-                    display_detailed_score(&boxscore, game.game_state);
+                    display_detailed_score(&boxscore, color);
                 }
                 Err(_) => {
                     // Fall back to simple display if boxscore unavailable
-                    display_simple_score(game);
+                    display_simple_score(game, color);
                 }
             }
         } else {
             // Game hasn't started yet
-            display_simple_score(game);
+            display_simple_score(game, color);
         }
     }
 
     println!();
+    Ok(())
 }
 
-fn display_detailed_score(boxscore: &Boxscore, game_state: nhl_api::GameState) {
+fn display_detailed_score(boxscore: &Boxscore, color: bool) {
     let away_abbrev = &boxscore.away_team.abbrev;
     let home_abbrev = &boxscore.home_team.abbrev;
     let away_score = boxscore.away_team.score;
@@ -67,7 +72,9 @@ fn display_detailed_score(boxscore: &Boxscore, game_state: nhl_api::GameState) {
 
     // Game status line
     let status_text = format_game_status(boxscore.game_state, &boxscore.period_descriptor.number, &boxscore.clock);
-    println!("│ {:<86} │", status_text);
+    // Pad before coloring so escape codes don't count toward the width
+    let highlight = if boxscore.game_state.is_final() { Highlight::Muted } else { Highlight::Live };
+    println!("│ {} │", paint(&format!("{:<86}", status_text), highlight, color));
 
     println!("├{:─<88}┤", "");
 
@@ -128,7 +135,7 @@ fn display_period_line(team_abbrev: &str, total_score: i32, max_period: i32) {
     println!("                                    │");
 }
 
-fn display_simple_score(game: &nhl_api::ScheduleGame) {
+fn display_simple_score(game: &nhl_api::ScheduleGame, color: bool) {
     println!("┌{:─<88}┐", "");
 
     if let (Some(away_score), Some(home_score)) = (game.away_team.score, game.home_team.score) {
@@ -144,7 +151,15 @@ fn display_simple_score(game: &nhl_api::ScheduleGame) {
     } else {
         format!("Status: {}", game.game_state)
     };
-    println!("│ {:<86} │", status);
+    let status = format!("{:<86}", status);
+    let status = if game.game_state.is_final() {
+        paint(&status, Highlight::Muted, color)
+    } else if game.game_state.has_started() {
+        paint(&status, Highlight::Live, color)
+    } else {
+        status
+    };
+    println!("│ {} │", status);
 
     println!("└{:─<88}┘", "");
 }
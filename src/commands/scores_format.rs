@@ -11,6 +11,7 @@ pub struct PeriodScores {
 }
 
 /// Format scores for TUI display with period-by-period breakdown
+#[allow(dead_code)] // Library API; the binary always passes a width
 pub fn format_scores_for_tui(
     schedule: &DailySchedule,
     period_scores: &HashMap<i64, PeriodScores>,
@@ -125,10 +126,10 @@ fn format_game_table(game: &ScheduleGame, period_scores: Option<&PeriodScores>,
             let period_text = match info.period_descriptor.period_type.as_str() {
                 "REG" => {
                     let ordinal = match info.period_descriptor.number {
-                        1 => "1st",
-                        2 => "2nd",
-                        3 => "3rd",
-                        n => return format!("{}th Period", n),
+                        1 => "1st".to_string(),
+                        2 => "2nd".to_string(),
+                        3 => "3rd".to_string(),
+                        n => format!("{}th", n),
                     };
                     format!("{} Period", ordinal)
                 },
@@ -165,13 +166,13 @@ fn format_game_table(game: &ScheduleGame, period_scores: Option<&PeriodScores>,
 
     // Determine current period for in-progress games
     let current_period_num = if game_started && !game.game_state.is_final() {
-        game_info.and_then(|info| {
+        game_info.map(|info| {
             // Get the current period number based on period type
             match info.period_descriptor.period_type.as_str() {
-                "REG" => Some(info.period_descriptor.number),
-                "OT" => Some(4),
-                "SO" => Some(5),
-                _ => Some(info.period_descriptor.number),
+                "REG" => info.period_descriptor.number,
+                "OT" => 4,
+                "SO" => 5,
+                _ => info.period_descriptor.number,
             }
         })
     } else {
@@ -230,6 +231,7 @@ fn format_game_table(game: &ScheduleGame, period_scores: Option<&PeriodScores>,
     output
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_score_table(
     away_team: &str,
     home_team: &str,
@@ -257,7 +259,7 @@ fn build_score_table(
         output.push('┬');
         output.push_str(&"─".repeat(4));
     }
-    output.push_str("╮");
+    output.push('╮');
 
     // Pad to max width
     // Calculate actual width: 1 (╭) + 5 (team) + (total_cols-1) * (1 connector + 4 dashes) + 1 (╮)
@@ -289,7 +291,7 @@ fn build_score_table(
 
     output.push('│');
     output.push_str(&format!("{:^4}", "T"));
-    output.push_str("│");
+    output.push('│');
 
     // Pad to max width
     // Calculate: 1 (│) + 5 (team) + (total_cols-1) * (1 │ + 4 chars) + 1 (│)
@@ -306,7 +308,7 @@ fn build_score_table(
         output.push('┼');
         output.push_str(&"─".repeat(4));
     }
-    output.push_str("┤");
+    output.push('┤');
 
     // Pad to max width
     let current_width = 1 + 5 + (total_cols - 1) * 5 + 1;
@@ -322,14 +324,14 @@ fn build_score_table(
 
     // Helper to check if a period should show score or dash
     let should_show_period = |period: i32| -> bool {
-        current_period_num.map_or(true, |current| period <= current)
+        current_period_num.is_none_or(|current| period <= current)
     };
 
     // Period scores or placeholders
     if let Some(periods) = away_periods {
         // Period 1
         let p1_value = if should_show_period(1) {
-            periods.get(0).map(|s| s.to_string()).unwrap_or_else(|| "-".to_string())
+            periods.first().map(|s| s.to_string()).unwrap_or_else(|| "-".to_string())
         } else {
             "-".to_string()
         };
@@ -392,7 +394,7 @@ fn build_score_table(
 
     output.push('│');
     output.push_str(&format!("{:^4}", away_score.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string()))); // Total
-    output.push_str("│");
+    output.push('│');
 
     // Pad to max width
     let current_width = 1 + 5 + (total_cols - 1) * 5 + 1;
@@ -410,7 +412,7 @@ fn build_score_table(
     if let Some(periods) = home_periods {
         // Period 1
         let p1_value = if should_show_period(1) {
-            periods.get(0).map(|s| s.to_string()).unwrap_or_else(|| "-".to_string())
+            periods.first().map(|s| s.to_string()).unwrap_or_else(|| "-".to_string())
         } else {
             "-".to_string()
         };
@@ -473,7 +475,7 @@ fn build_score_table(
 
     output.push('│');
     output.push_str(&format!("{:^4}", home_score.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string()))); // Total
-    output.push_str("│");
+    output.push('│');
 
     // Pad to max width
     let current_width = 1 + 5 + (total_cols - 1) * 5 + 1;
@@ -489,7 +491,7 @@ fn build_score_table(
         output.push('┴');
         output.push_str(&"─".repeat(4));
     }
-    output.push_str("╯");
+    output.push('╯');
 
     // Pad to max width
     let current_width = 1 + 5 + (total_cols - 1) * 5 + 1;
@@ -502,7 +504,7 @@ fn build_score_table(
 }
 
 /// Extract period scores from GameSummary
-pub fn extract_period_scores(summary: &GameSummary, away_team_id: i64, home_team_id: i64) -> PeriodScores {
+pub fn extract_period_scores(summary: &GameSummary, _away_team_id: i64, _home_team_id: i64) -> PeriodScores {
     let mut away_periods = vec![0, 0, 0]; // P1, P2, P3
     let mut home_periods = vec![0, 0, 0];
     let mut has_ot = false;
@@ -512,7 +514,7 @@ pub fn extract_period_scores(summary: &GameSummary, away_team_id: i64, home_team
     let mut prev_home_score = 0;
 
     for period in &summary.scoring {
-        let period_num = period.period_descriptor.number as usize;
+        let period_num = period.period_descriptor.number;
 
         // Determine if this is OT or SO
         if period.period_descriptor.period_type == "OT" {
@@ -542,7 +544,7 @@ pub fn extract_period_scores(summary: &GameSummary, away_team_id: i64, home_team
 
             // Store in the appropriate slot
             let idx = if period.period_descriptor.period_type == "REG" {
-                (period_num - 1).min(2) // P1=0, P2=1, P3=2
+                (period_num.clamp(1, 3) - 1) as usize // P1=0, P2=1, P3=2
             } else if period.period_descriptor.period_type == "OT" {
                 3 // OT slot
             } else if period.period_descriptor.period_type == "SO" {
@@ -1,6 +1,7 @@
-use nhl_api::{Client, GameDate, Standing};
-use chrono::NaiveDate;
+use nhl_api::{Client, Standing};
 use std::collections::BTreeMap;
+use unicode_width::UnicodeWidthStr;
+use super::formatting::SeparatorStyle;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GroupBy {
@@ -23,43 +24,197 @@ impl GroupBy {
     }
 }
 
-pub fn format_standings_table(standings: &[Standing]) -> String {
+/// A standings table column, which the teams can also be ordered by
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortColumn {
+    GamesPlayed,
+    Wins,
+    Losses,
+    OtLosses,
+    Points,
+}
+
+impl SortColumn {
+    pub fn name(&self) -> &str {
+        match self {
+            SortColumn::GamesPlayed => "GP",
+            SortColumn::Wins => "W",
+            SortColumn::Losses => "L",
+            SortColumn::OtLosses => "OT",
+            SortColumn::Points => "PTS",
+        }
+    }
+
+    /// The column to the right in the table, wrapping around to the first one
+    pub fn next(&self) -> SortColumn {
+        match self {
+            SortColumn::GamesPlayed => SortColumn::Wins,
+            SortColumn::Wins => SortColumn::Losses,
+            SortColumn::Losses => SortColumn::OtLosses,
+            SortColumn::OtLosses => SortColumn::Points,
+            SortColumn::Points => SortColumn::GamesPlayed,
+        }
+    }
+
+    /// The next of the shown `columns` to the right, wrapping around; itself if none is shown
+    pub fn next_shown(&self, columns: &[SortColumn]) -> SortColumn {
+        let mut column = self.next();
+        while column != *self {
+            if columns.contains(&column) {
+                return column;
+            }
+            column = column.next();
+        }
+        *self
+    }
+
+    /// Look up a column by its header ("GP", "pts", ...), case-insensitively
+    pub fn parse(name: &str) -> Option<SortColumn> {
+        SortColumn::all().into_iter().find(|column| column.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn all() -> [SortColumn; 5] {
+        [SortColumn::GamesPlayed, SortColumn::Wins, SortColumn::Losses, SortColumn::OtLosses, SortColumn::Points]
+    }
+
+    /// Display width of the column's values
+    fn width(&self) -> usize {
+        match self {
+            SortColumn::Points => 4,
+            _ => 3,
+        }
+    }
+
+    fn value(&self, standing: &Standing) -> i64 {
+        match self {
+            SortColumn::GamesPlayed => standing.games_played() as i64,
+            SortColumn::Wins => standing.wins as i64,
+            SortColumn::Losses => standing.losses as i64,
+            SortColumn::OtLosses => standing.ot_losses as i64,
+            SortColumn::Points => standing.points as i64,
+        }
+    }
+}
+
+/// Order of the teams within every standings group
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StandingsSort {
+    pub column: SortColumn,
+    pub descending: bool,
+}
+
+impl Default for StandingsSort {
+    fn default() -> Self {
+        StandingsSort {
+            column: SortColumn::Points,
+            descending: true,
+        }
+    }
+}
+
+impl StandingsSort {
+    /// Sort the teams, keeping the current order between ties
+    pub fn apply(&self, standings: &mut [Standing]) {
+        standings.sort_by(|a, b| {
+            let ordering = self.column.value(a).cmp(&self.column.value(b));
+            if self.descending { ordering.reverse() } else { ordering }
+        });
+    }
+}
+
+/// How standings tables are drawn
+#[derive(Debug, Clone)]
+pub struct TableFormat {
+    pub separator_style: SeparatorStyle,
+    /// Stat columns shown after the team name, in order
+    pub columns: Vec<SortColumn>,
+    /// Column whose header is marked with '>', while the TUI header row has focus
+    pub selected: Option<SortColumn>,
+}
+
+/// Parse the configured column names, skipping unknown ones; all columns if none are valid
+pub fn parse_columns(names: &[String]) -> Vec<SortColumn> {
+    let mut columns = Vec::new();
+    for name in names {
+        match SortColumn::parse(name) {
+            Some(column) if !columns.contains(&column) => columns.push(column),
+            Some(_) => {}
+            None => tracing::warn!("Ignoring unknown standings column '{}'", name),
+        }
+    }
+    if columns.is_empty() {
+        columns = SortColumn::all().to_vec();
+    }
+    columns
+}
+
+/// Show `column` if it's hidden, or hide it unless it's the last one shown.
+/// Returns whether it changed; the columns keep the table's order.
+pub fn toggle_column(columns: &mut Vec<SortColumn>, column: SortColumn) -> bool {
+    if columns.contains(&column) {
+        if columns.len() == 1 {
+            return false;
+        }
+        columns.retain(|shown| *shown != column);
+    } else {
+        columns.push(column);
+        columns.sort_by_key(|shown| SortColumn::all().iter().position(|c| c == shown));
+    }
+    true
+}
+
+pub fn format_standings_table(standings: &[Standing], quiet: bool, format: &TableFormat) -> String {
     let mut output = String::new();
 
-    // Print table header
-    output.push_str(&format!("{:<25} {:>3} {:>3} {:>3} {:>3} {:>4}\n", "Team", "GP", "W", "L", "OT", "PTS"));
-    output.push_str(&format!("{}\n", "─".repeat(46)));
+    // Print table header (omitted in quiet mode)
+    if !quiet {
+        let mut header = format!("{:<25}", "Team");
+        for column in &format.columns {
+            // Every column is wider than its name, leaving room for the marker
+            let name = if format.selected == Some(*column) { format!(">{}", column.name()) } else { column.name().to_string() };
+            header.push_str(&format!(" {:>width$}", name, width = column.width()));
+        }
+        let rule_width = header.width();
+        output.push_str(&header);
+        output.push('\n');
+        output.push_str(&format!("{}\n", format.separator_style.rule().repeat(rule_width)));
+    }
 
     // Print each team's stats
     for standing in standings {
-        let team_name = &standing.team_common_name.default;
-        output.push_str(&format!(
-            "{:<25} {:>3} {:>3} {:>3} {:>3} {:>4}\n",
-            team_name,
-            standing.games_played(),
-            standing.wins,
-            standing.losses,
-            standing.ot_losses,
-            standing.points
-        ));
+        let mut row = format!("{:<25}", standing.team_common_name.default);
+        for column in &format.columns {
+            row.push_str(&format!(" {:>width$}", column.value(standing), width = column.width()));
+        }
+        output.push_str(&row);
+        output.push('\n');
     }
 
     output
 }
 
-fn format_group_with_header(name: &str, teams: &[Standing]) -> Vec<String> {
+fn format_group_with_header(name: &str, teams: &[Standing], quiet: bool, format: &TableFormat) -> Vec<String> {
     let mut lines = Vec::new();
-    lines.push(format!("{}", name));
-    lines.push(format!("{}", "═".repeat(name.len())));
-    lines.push(String::new()); // Empty line between header and table
+    lines.push(name.to_string());
+    if !quiet {
+        // Underline by display width so accented or wide names line up
+        lines.push(format.separator_style.underline().repeat(name.width()));
+        lines.push(String::new()); // Empty line between header and table
+    }
 
     // Add table rows
-    let table = format_standings_table(teams);
+    let table = format_standings_table(teams, quiet, format);
     lines.extend(table.lines().map(|s| s.to_string()));
 
     lines
 }
 
+/// Width of a single standings table column
+const COLUMN_WIDTH: usize = 46;
+
+/// Minimum width needed to show two standings columns side by side (two columns plus the gap)
+pub const TWO_COLUMN_MIN_WIDTH: usize = COLUMN_WIDTH * 2 + 4;
+
 fn merge_columns(left_lines: Vec<String>, right_lines: Vec<String>, column_width: usize) -> String {
     let mut output = String::new();
     let max_len = left_lines.len().max(right_lines.len());
@@ -75,130 +230,473 @@ fn merge_columns(left_lines: Vec<String>, right_lines: Vec<String>, column_width
     output
 }
 
-pub fn format_standings_by_group(standings: &[Standing], by: GroupBy, western_first: bool) -> String {
-    if standings.is_empty() {
-        return "Loading standings...".to_string();
+/// Lay out two groups side by side, or stacked with a blank line between them
+fn layout_columns(left_lines: Vec<String>, right_lines: Vec<String>, two_columns: bool) -> String {
+    if two_columns {
+        return merge_columns(left_lines, right_lines, COLUMN_WIDTH);
     }
 
     let mut output = String::new();
-    let mut sorted_standings = standings.to_vec();
-    sorted_standings.sort_by(|a, b| b.points.cmp(&a.points));
+    for line in left_lines {
+        output.push_str(&line);
+        output.push('\n');
+    }
+    output.push('\n');
+    for line in right_lines {
+        output.push_str(&line);
+        output.push('\n');
+    }
+    output
+}
 
-    match by {
-        GroupBy::Division => {
-            let mut grouped: BTreeMap<String, Vec<_>> = BTreeMap::new();
-            for standing in sorted_standings {
-                grouped
-                    .entry(standing.division_name.clone())
-                    .or_default()
-                    .push(standing);
-            }
+/// Standings sorted (by points unless asked otherwise) and grouped once, so every layout reads the same grouping
+#[derive(Debug, Clone, Default)]
+pub struct StandingsGroups {
+    /// All teams, in sort order
+    pub league: Vec<Standing>,
+    /// Divisions of the Eastern conference (Atlantic, Metropolitan), alphabetically
+    pub eastern_divisions: Vec<(String, Vec<Standing>)>,
+    /// Divisions of the Western conference (Central, Pacific), alphabetically
+    pub western_divisions: Vec<(String, Vec<Standing>)>,
+    /// Divisions of any other (or unknown) conference, alphabetically (normally none)
+    pub other_divisions: Vec<(String, Vec<Standing>)>,
+    /// Conferences, alphabetically (Eastern, Western)
+    pub conferences: Vec<(String, Vec<Standing>)>,
+}
 
-            // Separate Eastern and Western divisions
-            let mut eastern_divs = Vec::new();
-            let mut western_divs = Vec::new();
+/// Conference a division belongs to, as reported by its teams' standings,
+/// falling back to the current alignment when the API leaves it out
+fn division_conference(division: &str, teams: &[Standing]) -> Option<String> {
+    if let Some(conference) = teams.iter().find_map(|standing| standing.conference_name.clone()) {
+        return Some(conference);
+    }
+    match division {
+        "Atlantic" | "Metropolitan" => Some("Eastern".to_string()),
+        "Central" | "Pacific" => Some("Western".to_string()),
+        _ => None,
+    }
+}
 
-            for (div_name, teams) in grouped {
-                if div_name == "Atlantic" || div_name == "Metropolitan" {
-                    eastern_divs.push((div_name, teams));
-                } else if div_name == "Central" || div_name == "Pacific" {
-                    western_divs.push((div_name, teams));
-                }
+impl StandingsGroups {
+    pub fn new(standings: &[Standing]) -> Self {
+        Self::sorted(standings, StandingsSort::default())
+    }
+
+    /// Group the standings with the teams of every group in `sort` order
+    pub fn sorted(standings: &[Standing], sort: StandingsSort) -> Self {
+        let mut league = standings.to_vec();
+        sort.apply(&mut league);
+
+        let mut divisions: BTreeMap<String, Vec<Standing>> = BTreeMap::new();
+        let mut conferences: BTreeMap<String, Vec<Standing>> = BTreeMap::new();
+        for standing in &league {
+            divisions
+                .entry(standing.division_name.clone())
+                .or_default()
+                .push(standing.clone());
+            let conference = standing.conference_name
+                .clone()
+                .unwrap_or_else(|| "Unknown".to_string());
+            conferences
+                .entry(conference)
+                .or_default()
+                .push(standing.clone());
+        }
+
+        // Separate Eastern and Western divisions (BTreeMap keeps them alphabetical)
+        let mut eastern_divisions = Vec::new();
+        let mut western_divisions = Vec::new();
+        let mut other_divisions = Vec::new();
+        for (div_name, teams) in divisions {
+            match division_conference(&div_name, &teams).as_deref() {
+                Some("Eastern") => eastern_divisions.push((div_name, teams)),
+                Some("Western") => western_divisions.push((div_name, teams)),
+                _ => other_divisions.push((div_name, teams)),
             }
+        }
 
-            // Sort divisions alphabetically within each conference
-            eastern_divs.sort_by(|a, b| a.0.cmp(&b.0));
-            western_divs.sort_by(|a, b| a.0.cmp(&b.0));
+        StandingsGroups {
+            league,
+            eastern_divisions,
+            western_divisions,
+            other_divisions,
+            conferences: conferences.into_iter().collect(),
+        }
+    }
 
+    pub fn is_empty(&self) -> bool {
+        self.league.is_empty()
+    }
+}
+
+pub fn format_standings_by_group(
+    standings: &[Standing],
+    by: GroupBy,
+    western_first: bool,
+    two_columns: bool,
+    quiet: bool,
+    format: &TableFormat,
+) -> String {
+    format_standings_groups(&StandingsGroups::new(standings), by, western_first, two_columns, quiet, format)
+}
+
+/// Stack a column of groups, each with its header, separated by blank lines
+fn format_stacked_groups(groups: &[(String, Vec<Standing>)], quiet: bool, format: &TableFormat) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (name, teams) in groups {
+        if !lines.is_empty() {
+            lines.push(String::new()); // Add blank line between groups
+        }
+        lines.extend(format_group_with_header(name, teams, quiet, format));
+    }
+    lines
+}
+
+pub fn format_standings_groups(
+    groups: &StandingsGroups,
+    by: GroupBy,
+    western_first: bool,
+    two_columns: bool,
+    quiet: bool,
+    format: &TableFormat,
+) -> String {
+    if groups.is_empty() {
+        return "Loading standings...".to_string();
+    }
+
+    let mut output = String::new();
+
+    match by {
+        GroupBy::Division if !groups.other_divisions.is_empty() => {
+            // Unexpected divisions can't be split into conference columns: list them all
+            // in one column rather than dropping the teams that don't fit
+            tracing::warn!(
+                divisions = ?groups.other_divisions.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(),
+                "unrecognized divisions, showing standings in a single column"
+            );
+            let mut all_divisions: Vec<_> = groups.eastern_divisions.iter()
+                .chain(&groups.western_divisions)
+                .chain(&groups.other_divisions)
+                .cloned()
+                .collect();
+            all_divisions.sort_by(|a, b| a.0.cmp(&b.0));
+
+            if !quiet {
+                output.push('\n');
+            }
+            for line in format_stacked_groups(&all_divisions, quiet, format) {
+                output.push_str(&line);
+                output.push('\n');
+            }
+        }
+        GroupBy::Division => {
             // Build column 1 and column 2 based on western_first
             let (col1_divs, col2_divs) = if western_first {
-                (western_divs, eastern_divs)
+                (&groups.western_divisions, &groups.eastern_divisions)
             } else {
-                (eastern_divs, western_divs)
+                (&groups.eastern_divisions, &groups.western_divisions)
             };
 
-            // Format each column as stacked divisions
-            let mut col1_lines = Vec::new();
-            for (div_name, teams) in &col1_divs {
-                if !col1_lines.is_empty() {
-                    col1_lines.push(String::new()); // Add blank line between divisions
-                }
-                col1_lines.extend(format_group_with_header(div_name, teams));
-            }
+            let col1_lines = format_stacked_groups(col1_divs, quiet, format);
+            let col2_lines = format_stacked_groups(col2_divs, quiet, format);
 
-            let mut col2_lines = Vec::new();
-            for (div_name, teams) in &col2_divs {
-                if !col2_lines.is_empty() {
-                    col2_lines.push(String::new()); // Add blank line between divisions
-                }
-                col2_lines.extend(format_group_with_header(div_name, teams));
+            if !quiet {
+                output.push('\n');
             }
-
-            output.push('\n');
-            output.push_str(&merge_columns(col1_lines, col2_lines, 46));
+            output.push_str(&layout_columns(col1_lines, col2_lines, two_columns));
         }
         GroupBy::Conference => {
-            let mut grouped: BTreeMap<String, Vec<_>> = BTreeMap::new();
-            for standing in sorted_standings {
-                let conference = standing.conference_name
-                    .clone()
-                    .unwrap_or_else(|| "Unknown".to_string());
-                grouped
-                    .entry(conference)
-                    .or_default()
-                    .push(standing);
-            }
-
-            // Convert to vec to split into columns
-            let mut groups: Vec<_> = grouped.into_iter().collect();
+            let mut conferences: Vec<_> = groups.conferences.iter().collect();
 
             // If western_first is true, reverse to show Western first
-            if western_first && groups.len() == 2 {
-                // BTreeMap gives us Eastern, Western alphabetically
+            if western_first && conferences.len() == 2 {
+                // Grouping gives us Eastern, Western alphabetically
                 // Reverse to get Western, Eastern
-                groups.reverse();
+                conferences.reverse();
             }
 
-            output.push('\n');
+            if !quiet {
+                output.push('\n');
+            }
 
-            if groups.len() == 2 {
-                let left_lines = format_group_with_header(&groups[0].0, &groups[0].1);
-                let right_lines = format_group_with_header(&groups[1].0, &groups[1].1);
-                output.push_str(&merge_columns(left_lines, right_lines, 46));
+            if conferences.len() == 2 {
+                let left_lines = format_group_with_header(&conferences[0].0, &conferences[0].1, quiet, format);
+                let right_lines = format_group_with_header(&conferences[1].0, &conferences[1].1, quiet, format);
+                output.push_str(&layout_columns(left_lines, right_lines, two_columns));
             } else {
                 // Fallback to single column if not exactly 2 conferences
-                for (conference, teams) in groups {
-                    output.push_str(&format!("\n{}\n", conference));
-                    output.push_str(&format!("{}\n", "═".repeat(conference.len())));
-                    output.push_str(&format_standings_table(&teams));
+                for (conference, teams) in conferences {
+                    if quiet {
+                        output.push_str(&format!("{}\n", conference));
+                    } else {
+                        output.push_str(&format!("\n{}\n", conference));
+                        output.push_str(&format!("{}\n", format.separator_style.underline().repeat(conference.width())));
+                    }
+                    output.push_str(&format_standings_table(teams, quiet, format));
                 }
             }
         }
         GroupBy::League => {
-            output.push('\n');
-            output.push_str(&format_standings_table(&sorted_standings));
+            if !quiet {
+                output.push('\n');
+            }
+            output.push_str(&format_standings_table(&groups.league, quiet, format));
         }
     }
 
     output
 }
 
-pub async fn run(client: &Client, season: Option<i64>, date: Option<String>, by: GroupBy) {
-    let standings = if let Some(date_str) = date {
+pub async fn run(
+    client: &Client,
+    season: Option<i64>,
+    date: Option<String>,
+    by: GroupBy,
+    western_first: bool,
+    quiet: bool,
+    format: &TableFormat,
+) -> Result<(), String> {
+    let result = if let Some(date_str) = date {
         // Parse date string and get standings for that date
-        let parsed_date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
-            .expect("Invalid date format. Use YYYY-MM-DD");
-        let game_date = GameDate::Date(parsed_date);
-        client.league_standings_for_date(&game_date).await.unwrap()
+        let game_date = super::parse_game_date(&date_str)?;
+        client.league_standings_for_date(&game_date).await
     } else if let Some(season_year) = season {
         // Get standings for specific season
-        client.league_standings_for_season(season_year).await.unwrap()
+        client.league_standings_for_season(season_year).await
     } else {
         // Get current standings
-        client.current_league_standings().await.unwrap()
+        client.current_league_standings().await
     };
+    let standings = result.map_err(|e| format!("Failed to fetch standings: {}", e))?;
 
-    // Use the shared formatting function (CLI always uses default order)
-    let output = format_standings_by_group(&standings, by, false);
+    // Use the shared formatting function, stacking the columns when the terminal is too narrow
+    let two_columns = super::terminal_width() >= TWO_COLUMN_MIN_WIDTH;
+    let output = format_standings_by_group(&standings, by, western_first, two_columns, quiet, format);
     print!("{}", output);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::standing;
+
+    fn table_format() -> TableFormat {
+        TableFormat {
+            separator_style: SeparatorStyle::Default,
+            columns: SortColumn::all().to_vec(),
+            selected: None,
+        }
+    }
+
+    /// Two teams in each of the four divisions, listed the way the API orders them (by points)
+    fn league() -> Vec<Standing> {
+        vec![
+            standing("FLA", Some("Eastern"), "Atlantic", 12, 3, 1),
+            standing("WPG", Some("Western"), "Central", 11, 4, 1),
+            standing("NJD", Some("Eastern"), "Metropolitan", 10, 5, 2),
+            standing("VGK", Some("Western"), "Pacific", 10, 5, 1),
+            standing("BOS", Some("Eastern"), "Atlantic", 9, 6, 1),
+            standing("DAL", Some("Western"), "Central", 8, 7, 2),
+            standing("NYR", Some("Eastern"), "Metropolitan", 8, 7, 1),
+            standing("EDM", Some("Western"), "Pacific", 7, 8, 1),
+        ]
+    }
+
+    #[test]
+    fn hiding_a_column_removes_it_from_the_table_columns() {
+        let mut columns = SortColumn::all().to_vec();
+        assert!(toggle_column(&mut columns, SortColumn::Losses));
+        assert_eq!(columns, vec![SortColumn::GamesPlayed, SortColumn::Wins, SortColumn::OtLosses, SortColumn::Points]);
+
+        // Showing it again puts it back in its place
+        assert!(toggle_column(&mut columns, SortColumn::Losses));
+        assert_eq!(columns, SortColumn::all().to_vec());
+    }
+
+    #[test]
+    fn the_last_shown_column_stays() {
+        let mut columns = vec![SortColumn::Points];
+        assert!(!toggle_column(&mut columns, SortColumn::Points));
+        assert_eq!(columns, vec![SortColumn::Points]);
+    }
+
+    #[test]
+    fn sorting_skips_hidden_columns() {
+        let columns = vec![SortColumn::GamesPlayed, SortColumn::OtLosses, SortColumn::Points];
+        assert_eq!(SortColumn::GamesPlayed.next_shown(&columns), SortColumn::OtLosses);
+        assert_eq!(SortColumn::Points.next_shown(&columns), SortColumn::GamesPlayed);
+        // A hidden column moves to the next shown one
+        assert_eq!(SortColumn::Wins.next_shown(&columns), SortColumn::OtLosses);
+        assert_eq!(SortColumn::Points.next_shown(&[SortColumn::Points]), SortColumn::Points);
+    }
+
+    #[test]
+    fn underline_matches_the_display_width() {
+        let format = table_format();
+        let lines = format_group_with_header("Montréal", &[], false, &format);
+        assert_eq!(lines[0], "Montréal");
+        assert_eq!(lines[1], "═".repeat(8));
+    }
+
+    #[test]
+    fn quiet_keeps_only_the_data_rows() {
+        let standings = league();
+        let format = table_format();
+
+        let full = format_standings_by_group(&standings, GroupBy::League, false, true, false, &format);
+        assert!(full.lines().any(|line| line.starts_with("Team")));
+        assert!(full.lines().any(|line| line.starts_with('─')));
+
+        let quiet = format_standings_by_group(&standings, GroupBy::League, false, true, true, &format);
+        let rows: Vec<&str> = quiet.lines().collect();
+        assert_eq!(rows.len(), standings.len());
+        for (row, standing) in rows.iter().zip(&standings) {
+            assert!(row.starts_with(&standing.team_abbrev.default));
+        }
+
+        let quiet = format_standings_by_group(&standings, GroupBy::Division, false, true, true, &format);
+        assert!(!quiet.contains("Team"));
+        assert!(!quiet.contains('─') && !quiet.contains('═'));
+        for standing in &standings {
+            assert!(quiet.contains(&standing.team_abbrev.default));
+        }
+    }
+
+    #[test]
+    fn western_first_swaps_the_columns() {
+        let standings = league();
+        let format = table_format();
+        let first_line = |by: GroupBy, western_first: bool| {
+            let output = format_standings_by_group(&standings, by, western_first, true, false, &format);
+            let line = output.lines().find(|line| !line.is_empty()).unwrap().to_string();
+            line.split_whitespace().map(str::to_string).collect::<Vec<_>>()
+        };
+
+        assert_eq!(first_line(GroupBy::Conference, false), ["Eastern", "Western"]);
+        assert_eq!(first_line(GroupBy::Conference, true), ["Western", "Eastern"]);
+        assert_eq!(first_line(GroupBy::Division, false), ["Atlantic", "Central"]);
+        assert_eq!(first_line(GroupBy::Division, true), ["Central", "Atlantic"]);
+    }
+
+    /// Output of the per-layout grouping that StandingsGroups replaced, for `league()`
+    fn pre_grouping_output(by: GroupBy) -> String {
+        let table = |rows: &[&str]| {
+            let mut table = format!("{:<25}  GP   W   L  OT  PTS\n{}\n", "Team", "─".repeat(46));
+            for row in rows {
+                table.push_str(row);
+                table.push('\n');
+            }
+            table
+        };
+        let group = |name: &str, rows: &[&str]| format!("{}\n{}\n\n{}", name, "═".repeat(name.len()), table(rows));
+        let fla = "FLA                        16  12   3   1   25";
+        let wpg = "WPG                        16  11   4   1   23";
+        let njd = "NJD                        17  10   5   2   22";
+        let vgk = "VGK                        16  10   5   1   21";
+        let bos = "BOS                        16   9   6   1   19";
+        let dal = "DAL                        17   8   7   2   18";
+        let nyr = "NYR                        16   8   7   1   17";
+        let edm = "EDM                        16   7   8   1   15";
+
+        match by {
+            GroupBy::Division => format!(
+                "\n{}\n{}\n{}\n{}",
+                group("Atlantic", &[fla, bos]),
+                group("Metropolitan", &[njd, nyr]),
+                group("Central", &[wpg, dal]),
+                group("Pacific", &[vgk, edm]),
+            ),
+            GroupBy::Conference => format!(
+                "\n{}\n{}",
+                group("Eastern", &[fla, njd, bos, nyr]),
+                group("Western", &[wpg, vgk, dal, edm]),
+            ),
+            GroupBy::League => format!("\n{}", table(&[fla, wpg, njd, vgk, bos, dal, nyr, edm])),
+        }
+    }
+
+    #[test]
+    fn grouping_once_keeps_every_layout() {
+        let standings = league();
+        let groups = StandingsGroups::new(&standings);
+        for by in GroupBy::all() {
+            assert_eq!(format_standings_groups(&groups, by, false, false, false, &table_format()), pre_grouping_output(by), "{:?}", by);
+        }
+    }
+
+    #[test]
+    fn unexpected_groups_fall_back_to_a_single_list() {
+        let mut standings = league();
+        standings.push(standing("XYZ", None, "Northwest", 5, 10, 1));
+        let groups = StandingsGroups::new(&standings);
+        assert_eq!(groups.other_divisions.len(), 1);
+        assert_eq!(groups.conferences.len(), 3);
+
+        let headings = |by: GroupBy| {
+            let output = format_standings_groups(&groups, by, false, true, true, &table_format());
+            // Quiet output is headings and team rows; only the headings are a single word
+            output.lines()
+                .filter(|line| line.split_whitespace().count() == 1)
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        // Every division in one alphabetical column, none dropped
+        assert_eq!(headings(GroupBy::Division), ["Atlantic", "Central", "Metropolitan", "Northwest", "Pacific"]);
+        assert_eq!(headings(GroupBy::Conference), ["Eastern", "Unknown", "Western"]);
+
+        let output = format_standings_groups(&groups, GroupBy::Conference, false, true, true, &table_format());
+        assert!(output.lines().any(|line| line.starts_with("XYZ")));
+    }
+
+    #[test]
+    fn divisions_map_to_their_conference() {
+        // The API's conference name wins
+        let teams = [standing("SEA", Some("Western"), "Northwest", 8, 7, 1)];
+        assert_eq!(division_conference("Northwest", &teams).as_deref(), Some("Western"));
+        let teams = [standing("BOS", Some("Eastern"), "Central", 8, 7, 1)];
+        assert_eq!(division_conference("Central", &teams).as_deref(), Some("Eastern"));
+
+        // Without one, the current alignment is used
+        for (division, conference) in [("Atlantic", "Eastern"), ("Metropolitan", "Eastern"), ("Central", "Western"), ("Pacific", "Western")] {
+            let teams = [standing("ABC", None, division, 8, 7, 1)];
+            assert_eq!(division_conference(division, &teams).as_deref(), Some(conference));
+        }
+        assert_eq!(division_conference("Northwest", &[standing("ABC", None, "Northwest", 8, 7, 1)]), None);
+        assert_eq!(division_conference("Pacific", &[]).as_deref(), Some("Western"));
+    }
+
+    #[test]
+    fn sorting_by_wins_keeps_ties_in_api_order() {
+        let standings = league();
+        let abbrevs = |sort: StandingsSort| {
+            StandingsGroups::sorted(&standings, sort).league.iter().map(|row| row.team_abbrev.default.clone()).collect::<Vec<_>>()
+        };
+
+        // NJD and VGK tie on 10 wins, DAL and NYR on 8: each pair stays in API order
+        let by_wins = StandingsSort { column: SortColumn::Wins, descending: true };
+        assert_eq!(abbrevs(by_wins), ["FLA", "WPG", "NJD", "VGK", "BOS", "DAL", "NYR", "EDM"]);
+
+        // Flipping the order (o) reverses the ranking, ties still in API order
+        let ascending = StandingsSort { descending: false, ..by_wins };
+        assert_eq!(abbrevs(ascending), ["EDM", "DAL", "NYR", "BOS", "NJD", "VGK", "WPG", "FLA"]);
+
+        let by_losses = StandingsSort { column: SortColumn::Losses, descending: true };
+        assert_eq!(abbrevs(by_losses)[..2], ["EDM", "DAL"]);
+    }
+
+    #[test]
+    fn selected_header_is_marked() {
+        let format = TableFormat { selected: Some(SortColumn::Wins), ..table_format() };
+        let table = format_standings_table(&league(), false, &format);
+        assert_eq!(table.lines().next(), Some("Team                       GP  >W   L  OT  PTS"));
+    }
+
+    #[test]
+    fn two_columns_fit_the_minimum_width() {
+        assert_eq!(TWO_COLUMN_MIN_WIDTH, 96);
+        let output = format_standings_by_group(&league(), GroupBy::Division, false, true, false, &table_format());
+        assert!(output.lines().all(|line| line.width() <= TWO_COLUMN_MIN_WIDTH));
+        assert!(output.lines().any(|line| line.starts_with("Atlantic") && line.ends_with("Central")));
+    }
 }
@@ -0,0 +1,48 @@
+//! Minimal API values for unit tests, built from the JSON the API sends
+
+use nhl_api::{DailySchedule, GameState, ScheduleGame, Standing};
+use serde_json::json;
+
+/// A game between `away` and `home` in the given state, without scores
+pub fn game(id: i64, away: &str, home: &str, game_state: GameState) -> ScheduleGame {
+    serde_json::from_value(json!({
+        "id": id,
+        "gameType": 2,
+        "startTimeUTC": "2024-11-02T23:00:00Z",
+        "awayTeam": { "id": id * 2, "abbrev": away, "placeName": null, "logo": "" },
+        "homeTeam": { "id": id * 2 + 1, "abbrev": home, "placeName": null, "logo": "" },
+        "gameState": game_state,
+    }))
+    .unwrap()
+}
+
+/// A day's schedule holding `games`
+pub fn schedule(games: Vec<ScheduleGame>) -> DailySchedule {
+    DailySchedule {
+        next_start_date: None,
+        previous_start_date: None,
+        date: "2024-11-02".to_string(),
+        number_of_games: games.len(),
+        games,
+    }
+}
+
+
+/// A standings row; points are two per win plus one per overtime loss
+pub fn standing(abbrev: &str, conference: Option<&str>, division: &str, wins: i32, losses: i32, ot_losses: i32) -> Standing {
+    serde_json::from_value(json!({
+        "conferenceAbbrev": conference.map(|name| &name[..1]),
+        "conferenceName": conference,
+        "divisionAbbrev": &division[..1],
+        "divisionName": division,
+        "teamName": { "default": abbrev },
+        "teamCommonName": { "default": abbrev },
+        "teamAbbrev": { "default": abbrev },
+        "teamLogo": "",
+        "wins": wins,
+        "losses": losses,
+        "otLosses": ot_losses,
+        "points": wins * 2 + ot_losses,
+    }))
+    .unwrap()
+}
@@ -2,8 +2,9 @@ use xdg::BaseDirectories;
 use serde::Deserialize;
 use std::fs;
 use std::path::PathBuf;
+use std::str::FromStr;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 #[serde(default)]
 pub struct Config {
     pub log_level: String,
@@ -11,6 +12,22 @@ pub struct Config {
     pub refresh_interval: u32,
     pub display_standings_western_first: bool,
     pub time_format: String,
+    pub screenshot_dir: String,
+    pub screenshot_name_template: String,
+    pub screenshot_format: String,
+    pub show_ticker: bool,
+    pub number_locale: String,
+    pub default_season: Option<i64>,
+    pub followed_teams: Vec<String>,
+    pub content_indent: usize,
+    pub request_timeout_secs: u64,
+    pub focus_color: String,
+    pub tabs: Vec<String>,
+    pub tab_nav_wrap: bool,
+    pub restore_session: bool,
+    pub standings_stack_width: usize,
+    pub separator_style: String,
+    pub standings_columns: Vec<String>,
 }
 
 impl Default for Config {
@@ -21,10 +38,96 @@ impl Default for Config {
             refresh_interval: 60,
             display_standings_western_first: false,
             time_format: "%H:%M:%S".to_string(),
+            screenshot_dir: ".".to_string(),
+            screenshot_name_template: "nhl-screenshot-{counter}.{ext}".to_string(),
+            screenshot_format: "text".to_string(),
+            show_ticker: false,
+            number_locale: "en-US".to_string(),
+            default_season: None,
+            followed_teams: Vec::new(),
+            content_indent: 2,
+            request_timeout_secs: 10,
+            focus_color: "cyan".to_string(),
+            tabs: vec!["scores".to_string(), "standings".to_string(), "settings".to_string()],
+            tab_nav_wrap: true,
+            restore_session: false,
+            standings_stack_width: 96,
+            separator_style: "default".to_string(),
+            standings_columns: ["GP", "W", "L", "OT", "PTS"].iter().map(|column| column.to_string()).collect(),
         }
     }
 }
 
+/// Commented default configuration written on first run
+const DEFAULT_CONFIG_FILE: &str = r#"# NHL configuration
+#
+# Every field can also be set with an NHL_-prefixed environment variable
+# (e.g. NHL_REFRESH_INTERVAL=30). Precedence: CLI flags > environment > this file > defaults.
+
+# Log level: trace, debug, info, warn, error
+log_level = "info"
+
+# Log file path (/dev/null disables logging)
+log_file = "/dev/null"
+
+# Seconds between automatic data refreshes
+refresh_interval = 60
+
+# Show the Western conference/divisions in the left column of the standings
+display_standings_western_first = false
+
+# strftime format for the "last refresh" time in the status bar
+time_format = "%H:%M:%S"
+
+# Where screenshots (Shift-S) are saved
+screenshot_dir = "."
+
+# Screenshot file name; supports {counter}, {date}, {tab} and {ext} (txt, ans or html, from screenshot_format)
+screenshot_name_template = "nhl-screenshot-{counter}.{ext}"
+
+# Screenshot format: text, ansi or html
+screenshot_format = "text"
+
+# Show a ticker of recent goals in live games at the bottom of the Scores tab
+show_ticker = false
+
+# Number formatting locale (decimal and grouping separators): en-US, en-CA, de-DE, fr-FR, fr-CA, sv-SE or fi-FI
+number_locale = "en-US"
+
+# Season used by commands given no --season, e.g. 20242025 (unset: current season)
+# default_season = 20242025
+
+# Teams shown by the "following" filter (f key), e.g. ["BOS", "TOR"]; empty shows every team
+followed_teams = []
+
+# Spaces of left indentation for the standings and scores content
+content_indent = 2
+
+# Seconds to wait for an NHL API response before giving up (must be at least 1)
+request_timeout_secs = 10
+
+# Color of the focused tab bar: a color name (e.g. cyan, lightblue) or #rrggbb; "none" uses the terminal's default
+focus_color = "cyan"
+
+# Tabs to show, in order (scores, standings, settings); unknown names are ignored
+tabs = ["scores", "standings", "settings"]
+
+# Whether Left/Right on the last/first tab wraps around to the other end
+tab_nav_wrap = true
+
+# Reopen the TUI on the tab, standings view and scores date it was closed on
+restore_session = false
+
+# Narrowest TUI content width that shows the standings in two columns; narrower stacks them
+standings_stack_width = 96
+
+# Glyph of table rules and heading underlines: default (─ rules, ═ underlines), single, double, dashed or ascii (ascii on a non-UTF-8 locale)
+separator_style = "default"
+
+# Stat columns of the standings tables, in order: GP, W, L, OT, PTS
+standings_columns = ["GP", "W", "L", "OT", "PTS"]
+"#;
+
 pub fn get_config_path() -> Option<PathBuf> {
     let pgm = env!("CARGO_PKG_NAME");
     let xdg_dirs = BaseDirectories::with_prefix(pgm);
@@ -32,24 +135,202 @@ pub fn get_config_path() -> Option<PathBuf> {
     Some(config_home.join("config.toml"))
 }
 
+/// Write the commented default config file if none exists yet.
+/// Returns the path of the created file, if one was written.
+pub fn init_if_missing() -> std::io::Result<Option<PathBuf>> {
+    match get_config_path() {
+        Some(config_path) => init_at(config_path),
+        None => Ok(None),
+    }
+}
+
+fn init_at(config_path: PathBuf) -> std::io::Result<Option<PathBuf>> {
+    if config_path.exists() {
+        return Ok(None);
+    }
+
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&config_path, DEFAULT_CONFIG_FILE)?;
+
+    Ok(Some(config_path))
+}
+
+/// Read the configuration, layering sources in order of precedence:
+/// CLI flags (applied by the caller) > environment variables > config file > defaults
 pub fn read() -> Config {
-    let config_path = match get_config_path() {
-        Some(path) => path,
-        None => return Config::default(),
-    };
+    let content = get_config_path().and_then(|path| fs::read_to_string(path).ok());
+    layered(content.as_deref(), &|name| std::env::var(name).ok())
+}
+
+/// The config file's settings (defaults when it's missing or invalid) with the
+/// environment overrides looked up through `env` applied on top
+fn layered(file_content: Option<&str>, env: &dyn Fn(&str) -> Option<String>) -> Config {
+    let mut config: Config = file_content
+        .and_then(|content| toml::from_str(content).ok())
+        .unwrap_or_default();
+    apply_env_overrides(&mut config, env);
+    // A zero timeout would fail every request
+    if config.request_timeout_secs == 0 {
+        config.request_timeout_secs = Config::default().request_timeout_secs;
+    }
+    config
+}
+
+/// Parse an environment variable, ignoring it if unset or invalid
+fn env_override<T: FromStr>(env: &dyn Fn(&str) -> Option<String>, name: &str) -> Option<T> {
+    env(name)?.trim().parse().ok()
+}
+
+/// Override config fields with `NHL_*` environment variables when they are set and valid
+fn apply_env_overrides(config: &mut Config, env: &dyn Fn(&str) -> Option<String>) {
+    if let Some(value) = env_override(env, "NHL_LOG_LEVEL") {
+        config.log_level = value;
+    }
+    if let Some(value) = env_override(env, "NHL_LOG_FILE") {
+        config.log_file = value;
+    }
+    if let Some(value) = env_override(env, "NHL_REFRESH_INTERVAL") {
+        config.refresh_interval = value;
+    }
+    if let Some(value) = env_override(env, "NHL_DISPLAY_STANDINGS_WESTERN_FIRST") {
+        config.display_standings_western_first = value;
+    }
+    if let Some(value) = env_override(env, "NHL_TIME_FORMAT") {
+        config.time_format = value;
+    }
+    if let Some(value) = env_override(env, "NHL_SCREENSHOT_DIR") {
+        config.screenshot_dir = value;
+    }
+    if let Some(value) = env_override(env, "NHL_SCREENSHOT_NAME_TEMPLATE") {
+        config.screenshot_name_template = value;
+    }
+    if let Some(value) = env_override(env, "NHL_SCREENSHOT_FORMAT") {
+        config.screenshot_format = value;
+    }
+    if let Some(value) = env_override(env, "NHL_SHOW_TICKER") {
+        config.show_ticker = value;
+    }
+    if let Some(value) = env_override(env, "NHL_NUMBER_LOCALE") {
+        config.number_locale = value;
+    }
+    if let Some(value) = env_override(env, "NHL_DEFAULT_SEASON") {
+        config.default_season = Some(value);
+    }
+    if let Some(value) = env("NHL_FOLLOWED_TEAMS") {
+        // Comma-separated list, e.g. NHL_FOLLOWED_TEAMS=BOS,TOR
+        config.followed_teams = value
+            .split(',')
+            .map(|team| team.trim().to_string())
+            .filter(|team| !team.is_empty())
+            .collect();
+    }
+    if let Some(value) = env_override(env, "NHL_CONTENT_INDENT") {
+        config.content_indent = value;
+    }
+    if let Some(value) = env_override(env, "NHL_REQUEST_TIMEOUT_SECS") {
+        config.request_timeout_secs = value;
+    }
+    if let Some(value) = env_override(env, "NHL_FOCUS_COLOR") {
+        config.focus_color = value;
+    }
+    if let Some(value) = env("NHL_TABS") {
+        // Comma-separated list, e.g. NHL_TABS=scores,standings
+        config.tabs = value
+            .split(',')
+            .map(|tab| tab.trim().to_string())
+            .filter(|tab| !tab.is_empty())
+            .collect();
+    }
+    if let Some(value) = env_override(env, "NHL_TAB_NAV_WRAP") {
+        config.tab_nav_wrap = value;
+    }
+    if let Some(value) = env_override(env, "NHL_RESTORE_SESSION") {
+        config.restore_session = value;
+    }
+    if let Some(value) = env_override(env, "NHL_STANDINGS_STACK_WIDTH") {
+        config.standings_stack_width = value;
+    }
+    if let Some(value) = env_override(env, "NHL_SEPARATOR_STYLE") {
+        config.separator_style = value;
+    }
+    if let Some(value) = env("NHL_STANDINGS_COLUMNS") {
+        // Comma-separated list, e.g. NHL_STANDINGS_COLUMNS=GP,PTS
+        config.standings_columns = value
+            .split(',')
+            .map(|column| column.trim().to_string())
+            .filter(|column| !column.is_empty())
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_of<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| vars.iter().find(|(var, _)| *var == name).map(|(_, value)| value.to_string())
+    }
+
+    #[test]
+    fn env_beats_file_beats_defaults() {
+        let file = "refresh_interval = 30\ntime_format = \"%H:%M\"\n";
+
+        let config = layered(None, &env_of(&[]));
+        assert_eq!(config.refresh_interval, Config::default().refresh_interval);
 
-    // Check if file exists
-    if !config_path.exists() {
-        return Config::default();
+        let config = layered(Some(file), &env_of(&[]));
+        assert_eq!(config.refresh_interval, 30);
+        assert_eq!(config.time_format, "%H:%M");
+
+        let config = layered(Some(file), &env_of(&[("NHL_REFRESH_INTERVAL", "45"), ("NHL_FOLLOWED_TEAMS", "BOS, TOR,")]));
+        assert_eq!(config.refresh_interval, 45);
+        assert_eq!(config.time_format, "%H:%M");
+        assert_eq!(config.followed_teams, vec!["BOS", "TOR"]);
     }
 
-    let content = match fs::read_to_string(&config_path) {
-        Ok(content) => content,
-        Err(_) => return Config::default(),
-    };
+    #[test]
+    fn invalid_env_value_keeps_the_file_value() {
+        let file = "refresh_interval = 30\nshow_ticker = true\n";
+        let config = layered(Some(file), &env_of(&[("NHL_REFRESH_INTERVAL", "soon"), ("NHL_SHOW_TICKER", "maybe")]));
+        assert_eq!(config.refresh_interval, 30);
+        assert!(config.show_ticker);
+    }
+
+    #[test]
+    fn invalid_file_falls_back_to_defaults() {
+        let config = layered(Some("refresh_interval = \"often\""), &env_of(&[]));
+        assert_eq!(config.refresh_interval, Config::default().refresh_interval);
+    }
+
+    #[test]
+    fn zero_request_timeout_uses_the_default() {
+        let config = layered(Some("request_timeout_secs = 0"), &env_of(&[]));
+        assert_eq!(config.request_timeout_secs, Config::default().request_timeout_secs);
+        let config = layered(None, &env_of(&[("NHL_REQUEST_TIMEOUT_SECS", "0")]));
+        assert_eq!(config.request_timeout_secs, Config::default().request_timeout_secs);
+    }
+
+    #[test]
+    fn default_config_file_matches_the_defaults() {
+        let config: Config = toml::from_str(DEFAULT_CONFIG_FILE).unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn init_creates_the_file_once() {
+        let dir = std::env::temp_dir().join(format!("nhl-config-test-{}", std::process::id()));
+        let path = dir.join("nested").join("config.toml");
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(init_at(path.clone()).unwrap(), Some(path.clone()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), DEFAULT_CONFIG_FILE);
+
+        fs::write(&path, "refresh_interval = 5\n").unwrap();
+        assert_eq!(init_at(path.clone()).unwrap(), None);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "refresh_interval = 5\n");
 
-    match toml::from_str(&content) {
-        Ok(config) => config,
-        Err(_) => Config::default(),
+        fs::remove_dir_all(&dir).unwrap();
     }
 }
@@ -0,0 +1,142 @@
+use nhl_api::NHLApiError;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// How long to stop fetching after the API rate-limits us
+pub const RATE_LIMIT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Time left in a rate-limit cooldown ending at `until`, if it hasn't ended yet
+pub fn cooldown_remaining(until: Option<Instant>, now: Instant) -> Option<Duration> {
+    until?.checked_duration_since(now).filter(|remaining| !remaining.is_zero())
+}
+
+/// Why a data fetch failed, so the UI can word (and retry) each case differently
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataError {
+    /// Connection or other transport failure
+    Network(String),
+    /// The requested resource doesn't exist (e.g. a bad game id)
+    NotFound(String),
+    /// The response couldn't be decoded
+    Parse(String),
+    /// The API asked us to slow down
+    RateLimited(String),
+    /// The request took longer than the configured timeout
+    Timeout,
+    /// Any other failure the API reported (bad request, unauthorized, ...)
+    Other(String),
+}
+
+impl From<&NHLApiError> for DataError {
+    fn from(error: &NHLApiError) -> Self {
+        match error {
+            NHLApiError::ResourceNotFound { message, .. } => DataError::NotFound(message.clone()),
+            NHLApiError::RateLimitExceeded { message, .. } => DataError::RateLimited(message.clone()),
+            NHLApiError::JsonError(e) => DataError::Parse(e.to_string()),
+            NHLApiError::RequestError(e) => DataError::from(e),
+            NHLApiError::ServerError { .. } | NHLApiError::ApiError { .. } => DataError::Network(error.to_string()),
+            NHLApiError::BadRequest { .. } | NHLApiError::Unauthorized { .. } | NHLApiError::Other(_) => {
+                DataError::Other(error.to_string())
+            }
+        }
+    }
+}
+
+impl From<&reqwest::Error> for DataError {
+    fn from(error: &reqwest::Error) -> Self {
+        if error.is_timeout() {
+            DataError::Timeout
+        } else if error.is_decode() {
+            DataError::Parse(error.to_string())
+        } else {
+            DataError::Network(error.to_string())
+        }
+    }
+}
+
+impl DataError {
+    /// Classify an error returned by the API client, which wraps either an
+    /// `NHLApiError` or the underlying `reqwest::Error` in an `anyhow::Error`
+    pub fn from_client_error(error: &anyhow::Error) -> DataError {
+        if let Some(api_error) = error.downcast_ref::<NHLApiError>() {
+            DataError::from(api_error)
+        } else if let Some(request_error) = error.downcast_ref::<reqwest::Error>() {
+            DataError::from(request_error)
+        } else {
+            DataError::Other(error.to_string())
+        }
+    }
+
+    /// Whether trying again later may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, DataError::Network(_) | DataError::RateLimited(_) | DataError::Timeout)
+    }
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataError::Network(message) => write!(f, "network error: {}", message),
+            DataError::NotFound(message) => write!(f, "not found: {}", message),
+            DataError::Parse(message) => write!(f, "unexpected response: {}", message),
+            DataError::RateLimited(_) => write!(f, "rate limited by the NHL API"),
+            DataError::Timeout => write!(f, "request timed out"),
+            DataError::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for DataError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_errors_map_to_their_kind() {
+        let not_found = NHLApiError::ResourceNotFound { message: "no game 1".to_string(), status_code: 404 };
+        assert_eq!(DataError::from(&not_found), DataError::NotFound("no game 1".to_string()));
+
+        let rate_limited = NHLApiError::RateLimitExceeded { message: "slow down".to_string(), status_code: 429 };
+        assert_eq!(DataError::from(&rate_limited), DataError::RateLimited("slow down".to_string()));
+
+        let server = NHLApiError::ServerError { message: "oops".to_string(), status_code: 503 };
+        assert!(matches!(DataError::from(&server), DataError::Network(_)));
+
+        let bad_request = NHLApiError::BadRequest { message: "bad".to_string(), status_code: 400 };
+        assert!(matches!(DataError::from(&bad_request), DataError::Other(_)));
+        assert!(matches!(DataError::from(&NHLApiError::Other("odd".to_string())), DataError::Other(_)));
+
+        let parse = serde_json::from_str::<i32>("not json").unwrap_err();
+        assert!(matches!(DataError::from(&NHLApiError::JsonError(parse)), DataError::Parse(_)));
+    }
+
+    #[test]
+    fn client_errors_are_unwrapped_before_mapping() {
+        let error = anyhow::Error::new(NHLApiError::RateLimitExceeded { message: "slow down".to_string(), status_code: 429 });
+        assert_eq!(DataError::from_client_error(&error), DataError::RateLimited("slow down".to_string()));
+        let error = anyhow::anyhow!("something else");
+        assert_eq!(DataError::from_client_error(&error), DataError::Other("something else".to_string()));
+    }
+
+    #[test]
+    fn only_transient_errors_are_retryable() {
+        assert!(DataError::Network("reset".to_string()).is_retryable());
+        assert!(DataError::RateLimited("slow down".to_string()).is_retryable());
+        assert!(DataError::Timeout.is_retryable());
+        assert!(!DataError::NotFound("no game".to_string()).is_retryable());
+        assert!(!DataError::Parse("bad json".to_string()).is_retryable());
+        assert!(!DataError::Other("bad request".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn cooldown_runs_until_its_end() {
+        let start = Instant::now();
+        let until = Some(start + RATE_LIMIT_COOLDOWN);
+        assert_eq!(cooldown_remaining(until, start), Some(RATE_LIMIT_COOLDOWN));
+        assert_eq!(cooldown_remaining(until, start + Duration::from_secs(59)), Some(Duration::from_secs(1)));
+        assert_eq!(cooldown_remaining(until, start + RATE_LIMIT_COOLDOWN), None);
+        assert_eq!(cooldown_remaining(until, start + RATE_LIMIT_COOLDOWN + Duration::from_secs(1)), None);
+        assert_eq!(cooldown_remaining(None, start), None);
+    }
+}
@@ -1,16 +1,21 @@
 mod tui;
 mod commands;
 mod config;
+mod data_error;
 
 use nhl_api::{Client, Standing, DailySchedule};
-use clap::{Parser, Subcommand, ValueEnum};
+use data_error::DataError;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use std::sync::Arc;
 use std::collections::HashMap;
 use tokio::sync::{RwLock, mpsc};
-use std::time::{Duration, SystemTime};
-use futures::future::join_all;
-use tracing::Level;
-use tracing_subscriber::FmtSubscriber;
+use std::time::{Duration, Instant, SystemTime};
+use std::future::Future;
+use futures::stream::{self, StreamExt};
+use tracing::{Instrument, Level};
+use tracing_subscriber::{EnvFilter, FmtSubscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::writer::{BoxMakeWriter, MakeWriterExt};
 
 #[derive(Clone)]
 pub struct SharedData {
@@ -22,6 +27,14 @@ pub struct SharedData {
     pub last_refresh: Option<SystemTime>,
     pub game_date: nhl_api::GameDate,
     pub error_message: Option<String>,
+    /// Bumped whenever the standings or schedule data change, so views know when to rebuild
+    pub data_version: u64,
+    /// Bumped when the user navigates to another date; fetches started under an older epoch are discarded
+    pub fetch_epoch: u64,
+    /// End of the cooldown after the API rate-limited us; no fetches are made before it
+    pub rate_limited_until: Option<Instant>,
+    /// Schedules of the current date and its neighbours, keyed by date, for instant date navigation
+    pub schedule_cache: HashMap<String, DailySchedule>,
 }
 
 impl Default for SharedData {
@@ -35,14 +48,39 @@ impl Default for SharedData {
             last_refresh: None,
             game_date: nhl_api::GameDate::today(),
             error_message: None,
+            data_version: 0,
+            fetch_epoch: 0,
+            rate_limited_until: None,
+            schedule_cache: HashMap::new(),
         }
     }
 }
 
 pub type SharedDataHandle = Arc<RwLock<SharedData>>;
 
+/// Which data a refresh fetches
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RefreshScope {
+    /// Standings, the selected date's schedule and its game details
+    All,
+    /// Only the standings
+    Standings,
+    /// Only the selected date's schedule and its game details
+    Schedule,
+}
+
+impl RefreshScope {
+    fn includes_standings(self) -> bool {
+        self != RefreshScope::Schedule
+    }
+
+    fn includes_schedule(self) -> bool {
+        self != RefreshScope::Standings
+    }
+}
+
 #[derive(Parser)]
-#[command(name = "nhl")]
+#[command(name = "nhl", version)]
 #[command(about = "NHL stats and standings CLI", long_about = "NHL stats and standings CLI\n\nIf no command is specified, the program starts in interactive mode.")]
 struct Cli {
     /// Set log level (trace, debug, info, warn, error)
@@ -53,10 +91,43 @@ struct Cli {
     #[arg(short = 'F', long, global = true, default_value = "/dev/null")]
     log_file: String,
 
+    /// Log output format
+    #[arg(long, global = true, default_value = "plain")]
+    log_format: LogFormat,
+
+    /// Per-module log filter, e.g. "nhl::commands=debug,info" (takes precedence over --log-level)
+    #[arg(long, global = true)]
+    log_filter: Option<String>,
+
+    /// Also write logs to stderr (ignored in interactive mode)
+    #[arg(long, global = true)]
+    log_stderr: bool,
+
+    /// Only print the core data, without headers, banners or status lines
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Disable colored output (also disabled by NO_COLOR or when output is piped)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Don't create a default config file on first run (or set NHL_NO_INIT_CONFIG)
+    #[arg(long, global = true)]
+    no_init_config: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum LogFormat {
+    /// Human-readable text lines
+    #[value(alias = "text")]
+    Plain,
+    /// One JSON object per line
+    Json,
+}
+
 #[derive(Clone, Copy, ValueEnum)]
 enum GroupBy {
     /// Group by division
@@ -74,140 +145,449 @@ enum GroupBy {
 enum Commands {
     /// Display NHL standings
     Standings {
-        /// Season year (optional, defaults to current standings)
+        /// Season id, e.g. 20242025 (optional, defaults to default_season or current standings)
         #[arg(short, long)]
         season: Option<i64>,
 
-        /// Date in YYYY-MM-DD format (optional)
-        #[arg(short, long)]
+        /// Date: YYYY-MM-DD, today, yesterday, tomorrow, or an offset like +1/-2 (optional)
+        #[arg(short, long, allow_hyphen_values = true)]
         date: Option<String>,
 
         /// Group standings by: d=division, c=conference, l=league
         #[arg(short, long, default_value = "d")]
         by: GroupBy,
+
+        /// Show the Western conference/divisions in the left column (overrides config)
+        #[arg(long, conflicts_with = "eastern_first")]
+        western_first: bool,
+
+        /// Show the Eastern conference/divisions in the left column (overrides config)
+        #[arg(long)]
+        eastern_first: bool,
+
+        /// Re-run the command periodically, clearing the screen between runs
+        #[arg(short, long)]
+        watch: bool,
+
+        /// Seconds between runs in watch mode (defaults to refresh_interval)
+        #[arg(short, long)]
+        interval: Option<u64>,
     },
     /// Display boxscore for a specific game
     Boxscore {
         /// Game ID (e.g., 2024020001)
         game_id: i64,
+
+        /// Add per-60 rate columns (P/60, S/60) to the skater tables
+        #[arg(short, long)]
+        rates: bool,
     },
     /// Display daily schedule of games
     Schedule {
-        /// Date in YYYY-MM-DD format (optional, defaults to today)
-        #[arg(short, long)]
+        /// Date: YYYY-MM-DD, today, yesterday, tomorrow, or an offset like +1/-2 (defaults to today)
+        #[arg(short, long, allow_hyphen_values = true)]
         date: Option<String>,
+
+        /// Only show games involving this team (e.g., BOS)
+        #[arg(short, long)]
+        team: Option<String>,
+
+        /// Show a whole week: YYYY-Www, or the week of --date (or today) when no value is given
+        #[arg(short, long, num_args = 0..=1, value_name = "YYYY-Www")]
+        week: Option<Option<String>>,
     },
     /// Display scores for games with period-by-period breakdown
     Scores {
-        /// Date in YYYY-MM-DD format (optional, defaults to today)
-        #[arg(short, long)]
+        /// Date: YYYY-MM-DD, today, yesterday, tomorrow, or an offset like +1/-2 (defaults to today)
+        #[arg(short, long, allow_hyphen_values = true)]
         date: Option<String>,
+
+        /// Only show games involving this team (e.g., BOS)
+        #[arg(short, long)]
+        team: Option<String>,
+
+        /// Re-run the command periodically, clearing the screen between runs
+        #[arg(short, long)]
+        watch: bool,
+
+        /// Seconds between runs in watch mode (defaults to refresh_interval)
+        #[arg(short, long)]
+        interval: Option<u64>,
     },
     /// Display current configuration
     Config,
+    /// Display version and environment details for bug reports
+    Info,
+    /// Print a shell completion script
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+    },
+    /// Print a roff man page
+    Man,
+}
+
+/// Base URL of the NHL web API queried by nhl_api
+const NHL_API_BASE_URL: &str = "https://api-web.nhle.com/v1";
+
+/// Config file path for display, and whether it exists
+fn config_path_status() -> (String, bool) {
+    match config::get_config_path() {
+        Some(path) => {
+            let exists = path.exists();
+            (path.display().to_string(), exists)
+        }
+        None => ("Unable to determine config path".to_string(), false),
+    }
+}
+
+/// Write the completion script of `shell` for the whole command line
+fn write_completions(shell: clap_complete::Shell, out: &mut dyn std::io::Write) {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, out);
+}
+
+/// Render the nhl(1) man page
+fn write_man_page(out: &mut dyn std::io::Write) -> std::io::Result<()> {
+    clap_mangen::Man::new(Cli::command()).render(out)
+}
+
+fn format_info(config_path: &str, config_exists: bool, config: &config::Config) -> String {
+    let mut output = String::new();
+    output.push_str(&format!("{} {}\n", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")));
+    output.push_str(&format!("Data source: {}\n", NHL_API_BASE_URL));
+    output.push_str(&format!("Configuration File: {} (Exists: {})\n", config_path, if config_exists { "yes" } else { "no" }));
+    output.push_str(&format!("Current season: {}\n", commands::current_season()));
+    output.push_str(&format!("Log level: {}\n", config.log_level));
+    output.push_str(&format!("Log file: {}\n", config.log_file));
+    output
+}
+
+/// Fetch the schedules of the days before and after `date` into the schedule cache,
+/// giving up as soon as the user navigates away, and drop cached dates further out.
+/// Days already cached aren't fetched again; the main refresh keeps the current day fresh.
+async fn prefetch_adjacent_schedules<F, Fut>(fetch: F, shared_data: &SharedDataHandle, date: &nhl_api::GameDate, epoch: u64, timeout: Duration)
+where
+    F: Fn(nhl_api::GameDate) -> Fut,
+    Fut: Future<Output = anyhow::Result<DailySchedule>>,
+{
+    let date_key = game_date_key(date);
+    let mut keep = vec![date_key.clone()];
+
+    for neighbour in [date.add_days(-1), date.add_days(1)] {
+        let key = game_date_key(&neighbour);
+        keep.push(key.clone());
+        if shared_data.read().await.schedule_cache.contains_key(&key) {
+            continue;
+        }
+        // Navigation takes priority over prefetching, and nothing is sent during a cooldown
+        if shared_data.read().await.fetch_epoch != epoch || rate_limited(shared_data).await {
+            return;
+        }
+        if let Ok(schedule) = timed_fetch("schedule_prefetch", &key, timeout, fetch(neighbour)).await {
+            shared_data.write().await.schedule_cache.insert(key, schedule);
+        }
+    }
+
+    shared_data.write().await.schedule_cache.retain(|key, _| keep.contains(key));
+}
+
+/// Most game detail requests in flight at once when a schedule loads
+const MAX_CONCURRENT_GAME_FETCHES: usize = 4;
+
+/// Run `fetch` for every item, at most MAX_CONCURRENT_GAME_FETCHES at a time, yielding results as they complete
+fn fetch_concurrently<I, F, Fut>(items: I, fetch: F) -> impl futures::Stream<Item = Fut::Output>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future,
+{
+    stream::iter(items).map(fetch).buffer_unordered(MAX_CONCURRENT_GAME_FETCHES)
 }
 
 /// Create an NHL API client with optional debug mode
 fn create_client() -> Client {
-    Client::new().unwrap()
+    Client::new().unwrap_or_else(|e| commands::exit_with_error(&format!("Failed to create NHL API client: {}", e)))
+}
+
+/// Milliseconds elapsed since `start`
+fn elapsed_ms(start: Instant) -> u64 {
+    start.elapsed().as_millis() as u64
 }
 
-async fn fetch_data_loop(client: Client, shared_data: SharedDataHandle, interval: u64, mut refresh_rx: mpsc::Receiver<()>) {
+/// Key identifying a date in fetch logs and the schedule cache ("now" resolves to today's date)
+fn game_date_key(date: &nhl_api::GameDate) -> String {
+    commands::naive_date(date).format("%Y-%m-%d").to_string()
+}
+
+/// Run a data fetch inside a `fetch` span, giving up after `timeout` and logging its duration and outcome
+async fn timed_fetch<T, F>(resource: &str, key: &str, timeout: Duration, fetch: F) -> Result<T, DataError>
+where
+    F: Future<Output = anyhow::Result<T>>,
+{
+    let span = tracing::debug_span!("fetch", resource, key);
+    let start = Instant::now();
+    let result = match tokio::time::timeout(timeout, fetch.instrument(span.clone())).await {
+        Ok(result) => result.map_err(|e| DataError::from_client_error(&e)),
+        Err(_) => Err(DataError::Timeout),
+    };
+    let duration_ms = elapsed_ms(start);
+
+    span.in_scope(|| match &result {
+        Ok(_) => tracing::debug!(duration_ms, success = true, "fetch completed"),
+        Err(e) => tracing::warn!(duration_ms, success = false, error = %e, "fetch failed"),
+    });
+
+    result
+}
+
+/// Status bar text for a failed fetch, noting when the next refresh may fix it
+fn fetch_error_message(what: &str, error: &DataError) -> String {
+    if error.is_retryable() {
+        format!("Failed to fetch {}: {} (retrying at next refresh)", what, error)
+    } else {
+        format!("Failed to fetch {}: {}", what, error)
+    }
+}
+
+/// Record a failed fetch, starting a cooldown if the API rate-limited us
+fn record_fetch_error(shared: &mut SharedData, what: &str, error: &DataError) {
+    if let DataError::RateLimited(_) = error {
+        shared.rate_limited_until = Some(Instant::now() + data_error::RATE_LIMIT_COOLDOWN);
+    }
+    shared.error_message = Some(fetch_error_message(what, error));
+}
+
+/// Whether a rate-limit cooldown is in effect, in which case no request should be sent
+async fn rate_limited(shared_data: &SharedDataHandle) -> bool {
+    data_error::cooldown_remaining(shared_data.read().await.rate_limited_until, Instant::now()).is_some()
+}
+
+/// Fetch the schedule of the selected date, then the details of its started games
+/// and the neighbouring dates' schedules. Stops early when the user moves to another
+/// date (its own refresh follows) or the API starts rate-limiting.
+/// Store a freshly fetched schedule, keeping the cached details of games that are still on it.
+/// Returns false, storing nothing, if the user moved to another date since `epoch`.
+fn store_schedule(shared: &mut SharedData, epoch: u64, date_key: &str, schedule: DailySchedule) -> bool {
+    if shared.fetch_epoch != epoch {
+        return false;
+    }
+    shared.period_scores.retain(|id, _| schedule.games.iter().any(|game| game.id == *id));
+    shared.game_info.retain(|id, _| schedule.games.iter().any(|game| game.id == *id));
+    shared.schedule_cache.insert(date_key.to_string(), schedule.clone());
+    shared.schedule = Some(schedule);
+    shared.data_version += 1;
+    // A schedule-only refresh counts as a refresh too
+    shared.last_refresh = Some(SystemTime::now());
+    true
+}
+
+async fn refresh_schedule(client: &Client, shared_data: &SharedDataHandle, request_timeout: Duration) {
+    if rate_limited(shared_data).await {
+        return;
+    }
+    let (date, epoch) = {
+        let shared = shared_data.read().await;
+        (shared.game_date.clone(), shared.fetch_epoch)
+    };
+    let date_key = game_date_key(&date);
+    let schedule = match timed_fetch("schedule", &date_key, request_timeout, client.daily_schedule(Some(date.clone()))).await {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            let mut shared = shared_data.write().await;
+            record_fetch_error(&mut shared, "schedule", &e);
+            return;
+        }
+    };
+
+    // Show the schedule right away, keeping the cached details of games
+    // that are still on it; details for started games stream in below
+    let games_to_fetch: Vec<_> = schedule.games.iter()
+        .filter(|game| game.game_state.has_started())
+        .cloned()
+        .collect();
+    if !store_schedule(&mut *shared_data.write().await, epoch, &date_key, schedule) {
+        return;
+    }
+
+    // Fetch period scores and game info for LIVE and FINAL games, a few at a time
+    let mut results = fetch_concurrently(games_to_fetch, |game| async move {
+        // Requests queued behind a rate-limited one are not sent
+        if rate_limited(shared_data).await {
+            return (game, None);
+        }
+        let game_id = nhl_api::GameId::new(game.id);
+        let game_key = game.id.to_string();
+        let result = timed_fetch("landing", &game_key, request_timeout, client.landing(&game_id)).await;
+        (game, Some(result))
+    });
+
+    // Store each result as it completes
+    while let Some((game, result)) = results.next().await {
+        let mut shared = shared_data.write().await;
+        // The user moved to another date: drop the remaining requests
+        if shared.fetch_epoch != epoch {
+            return;
+        }
+        match result {
+            Some(Ok(landing)) => {
+                if let Some(summary) = &landing.summary {
+                    let scores = commands::scores_format::extract_period_scores(
+                        summary,
+                        game.away_team.id,
+                        game.home_team.id,
+                    );
+                    shared.period_scores.insert(game.id, scores);
+                }
+                // Store the full game info for clock/period display
+                shared.game_info.insert(game.id, landing);
+                shared.data_version += 1;
+            }
+            Some(Err(e)) => {
+                // Store error for individual game fetch failures
+                record_fetch_error(&mut shared, &format!("game {} data", game.id), &e);
+            }
+            None => {}
+        }
+    }
+
+    // Prefetch the neighbouring dates so date navigation can show them immediately
+    prefetch_adjacent_schedules(|day| client.daily_schedule(Some(day)), shared_data, &date, epoch, request_timeout).await;
+}
+
+async fn fetch_data_loop(client: Client, shared_data: SharedDataHandle, interval: u64, request_timeout: Duration, mut refresh_rx: mpsc::Receiver<RefreshScope>) {
     let mut interval_timer = tokio::time::interval(Duration::from_secs(interval));
     interval_timer.tick().await; // First tick completes immediately
+    let mut scope = RefreshScope::All;
 
     loop {
+        // Sit out a rate-limit cooldown before hitting the API again
+        let cooldown = data_error::cooldown_remaining(shared_data.read().await.rate_limited_until, Instant::now());
+        if let Some(remaining) = cooldown {
+            tokio::time::sleep(remaining).await;
+        }
+
         // Fetch standings
-        match client.current_league_standings().await {
-            Ok(data) => {
-                let mut shared = shared_data.write().await;
-                shared.standings = data;
-                shared.last_refresh = Some(SystemTime::now());
-                shared.error_message = None; // Clear any previous errors
-            }
-            Err(e) => {
-                let mut shared = shared_data.write().await;
-                shared.error_message = Some(format!("Failed to fetch standings: {}", e));
+        if scope.includes_standings() {
+            match timed_fetch("standings", "current", request_timeout, client.current_league_standings()).await {
+                Ok(data) => {
+                    let mut shared = shared_data.write().await;
+                    shared.standings = data;
+                    shared.data_version += 1;
+                    shared.last_refresh = Some(SystemTime::now());
+                    shared.error_message = None; // Clear any previous errors
+                }
+                Err(e) => {
+                    let mut shared = shared_data.write().await;
+                    record_fetch_error(&mut shared, "standings", &e);
+                }
             }
         }
 
         // Fetch schedule for the current game_date
-        let date = {
-            let shared = shared_data.read().await;
-            shared.game_date.clone()
-        };
-        match client.daily_schedule(Some(&date)).await {
-            Ok(schedule) => {
-                // Fetch period scores and game info for LIVE and FINAL games
-                let mut period_scores = HashMap::new();
-                let mut game_info = HashMap::new();
-
-                // Collect all games that need fetching
-                let games_to_fetch: Vec<_> = schedule.games.iter()
-                    .filter(|game| game.game_state.has_started())
-                    .collect();
-
-                // Create futures for all landing requests
-                let fetch_futures = games_to_fetch.iter().map(|game| {
-                    let game_id = nhl_api::GameId::new(game.id);
-                    let game_clone = (*game).clone();
-                    let client_ref = &client;
-                    async move {
-                        let result = client_ref.landing(&game_id).await;
-                        (game_clone, result)
-                    }
-                });
-
-                // Execute all requests in parallel
-                let results = join_all(fetch_futures).await;
-
-                // Process results
-                for (game, result) in results {
-                    match result {
-                        Ok(landing) => {
-                            if let Some(summary) = &landing.summary {
-                                let scores = commands::scores_format::extract_period_scores(
-                                    summary,
-                                    game.away_team.id,
-                                    game.home_team.id,
-                                );
-                                period_scores.insert(game.id, scores);
-                            }
-                            // Store the full game info for clock/period display
-                            game_info.insert(game.id, landing);
-                        }
-                        Err(e) => {
-                            // Store error for individual game fetch failures
-                            let mut shared = shared_data.write().await;
-                            shared.error_message = Some(format!("Failed to fetch game {} data: {}", game.id, e));
-                        }
-                    }
-                }
-
-                let mut shared = shared_data.write().await;
-                shared.schedule = Some(schedule);
-                shared.period_scores = period_scores;
-                shared.game_info = game_info;
-                // Note: errors from individual game fetches are preserved
-            }
-            Err(e) => {
-                let mut shared = shared_data.write().await;
-                shared.error_message = Some(format!("Failed to fetch schedule: {}", e));
-            }
+        if scope.includes_schedule() {
+            refresh_schedule(&client, &shared_data, request_timeout).await;
         }
+
         // Wait for either the interval timer or a manual refresh signal
         tokio::select! {
             _ = interval_timer.tick() => {
                 // Regular interval refresh
+                scope = RefreshScope::All;
             }
-            _ = refresh_rx.recv() => {
+            Some(requested) = refresh_rx.recv() => {
                 // Manual refresh triggered
+                scope = requested;
+            }
+        }
+    }
+}
+
+/// Fallback watch interval when neither the flag nor the config provide one
+const DEFAULT_WATCH_INTERVAL: u64 = 60;
+
+/// Watch interval in seconds: --interval flag, then config refresh_interval, then the default
+fn resolve_watch_interval(flag: Option<u64>, refresh_interval: u32) -> u64 {
+    match flag {
+        Some(secs) if secs > 0 => secs,
+        _ if refresh_interval > 0 => refresh_interval as u64,
+        _ => DEFAULT_WATCH_INTERVAL,
+    }
+}
+
+/// Re-run a command every `interval` seconds until Ctrl-C; a failed run is reported
+/// and tried again on the next tick
+async fn run_watch<F, Fut>(interval: u64, mut run: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(), String>>,
+{
+    loop {
+        // Clear the screen and move the cursor home
+        print!("\x1B[2J\x1B[H");
+
+        tokio::select! {
+            result = run() => {
+                if let Err(e) = result {
+                    eprintln!("{}", e);
+                }
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(interval)) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+}
+
+/// Validate an optional --team flag, exiting with an error before any fetch if it's unknown
+fn validate_team_flag(team: Option<String>) -> Option<String> {
+    team.map(|abbrev| commands::validate_team_abbrev(&abbrev).unwrap_or_else(|e| commands::exit_with_error(&e)))
+}
+
+/// Pick where log lines go: the log file, stderr, both, or nowhere
+fn select_log_writer(log_file: &str, log_stderr: bool) -> Option<BoxMakeWriter> {
+    // /dev/null means no log file
+    let file = if log_file == "/dev/null" {
+        None
+    } else {
+        match std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_file)
+        {
+            Ok(f) => Some(f),
+            Err(e) => {
+                eprintln!("Failed to open log file {}: {}", log_file, e);
+                None
             }
         }
+    };
+
+    match (file, log_stderr) {
+        (Some(file), true) => Some(BoxMakeWriter::new(std::sync::Mutex::new(file).and(std::io::stderr))),
+        (Some(file), false) => Some(BoxMakeWriter::new(std::sync::Mutex::new(file))),
+        (None, true) => Some(BoxMakeWriter::new(std::io::stderr)),
+        (None, false) => None,
+    }
+}
+
+/// Build the log filter from a directive string, falling back to the global level
+/// when no filter is given or it fails to parse
+fn build_log_filter(log_filter: Option<&str>, level: Level) -> EnvFilter {
+    if let Some(directives) = log_filter {
+        match EnvFilter::try_new(directives) {
+            Ok(filter) => return filter,
+            Err(e) => eprintln!("Invalid log filter '{}': {} (using level {})", directives, e, level),
+        }
     }
+    EnvFilter::default().add_directive(LevelFilter::from_level(level).into())
 }
 
-fn init_logging(log_level: &str, log_file: &str) {
+fn init_logging(log_level: &str, log_filter: Option<&str>, log_file: &str, log_format: LogFormat, log_stderr: bool) {
     // Parse log level
     let level = match log_level.to_lowercase().as_str() {
         "trace" => Level::TRACE,
@@ -218,41 +598,52 @@ fn init_logging(log_level: &str, log_file: &str) {
         _ => Level::INFO,
     };
 
-    // If log file is /dev/null, skip logging setup
-    if log_file == "/dev/null" {
-        return;
-    }
-
-    // Create log file
-    let file = match std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(log_file)
-    {
-        Ok(f) => f,
-        Err(e) => {
-            eprintln!("Failed to open log file {}: {}", log_file, e);
-            return;
-        }
+    // Skip logging setup when there's nowhere to write to
+    let writer = match select_log_writer(log_file, log_stderr) {
+        Some(writer) => writer,
+        None => return,
     };
 
-    // Initialize tracing subscriber with file output
-    let subscriber = FmtSubscriber::builder()
-        .with_max_level(level)
-        .with_writer(std::sync::Mutex::new(file))
-        .with_ansi(false)
-        .finish();
-
+    // Initialize tracing subscriber with the selected output
+    let subscriber = build_subscriber(build_log_filter(log_filter, level), writer, log_format);
     if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
         eprintln!("Failed to set tracing subscriber: {}", e);
     }
 }
 
+/// Subscriber writing `log_format` lines to `writer`
+fn build_subscriber(filter: EnvFilter, writer: BoxMakeWriter, log_format: LogFormat) -> Box<dyn tracing::Subscriber + Send + Sync> {
+    let builder = FmtSubscriber::builder()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(false);
+
+    match log_format {
+        LogFormat::Plain => Box::new(builder.finish()),
+        LogFormat::Json => Box::new(builder.json().finish()),
+    }
+}
+
 #[tokio::main]
 async fn main() {
-    let mut config = config::read();
     let cli = Cli::parse();
 
+    // First run: write a commented default config so users can discover the options.
+    // Only the TUI and the data commands do; config, info, completions and man don't.
+    let creates_config = matches!(
+        cli.command,
+        None | Some(Commands::Standings { .. } | Commands::Boxscore { .. } | Commands::Schedule { .. } | Commands::Scores { .. })
+    );
+    if creates_config && !cli.no_init_config && std::env::var_os("NHL_NO_INIT_CONFIG").is_none() {
+        match config::init_if_missing() {
+            Ok(Some(path)) => eprintln!("Created default configuration file: {}", path.display()),
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to create default configuration file: {}", e),
+        }
+    }
+
+    let mut config = config::read();
+
     // CLI arguments override config file
     let log_level = if cli.log_level != "info" {
         &cli.log_level
@@ -265,8 +656,36 @@ async fn main() {
         &config.log_file
     };
 
+    // Logging to stderr would corrupt the TUI's alternate screen
+    let log_stderr = if cli.log_stderr && cli.command.is_none() {
+        eprintln!("--log-stderr is ignored in interactive mode");
+        false
+    } else {
+        cli.log_stderr
+    };
+
     // Initialize logging
-    init_logging(log_level, log_file);
+    init_logging(log_level, cli.log_filter.as_deref(), log_file, cli.log_format, log_stderr);
+
+    // Unknown followed teams are dropped so the filter only matches real teams
+    config.followed_teams = config.followed_teams
+        .iter()
+        .filter_map(|team| match commands::validate_team_abbrev(team) {
+            Ok(abbrev) => Some(abbrev),
+            Err(_) => {
+                eprintln!("Ignoring unknown team '{}' in followed_teams", team);
+                None
+            }
+        })
+        .collect();
+
+    // An invalid pinned season is ignored rather than sent to the API
+    if let Some(season) = config.default_season {
+        if let Err(e) = commands::validate_season(season) {
+            eprintln!("Ignoring default_season: {}", e);
+            config.default_season = None;
+        }
+    }
 
     // If no subcommand, run TUI
     if cli.command.is_none() {
@@ -280,10 +699,14 @@ async fn main() {
             last_refresh: None,
             game_date: nhl_api::GameDate::today(),
             error_message: None,
+            data_version: 0,
+            fetch_epoch: 0,
+            rate_limited_until: None,
+            schedule_cache: HashMap::new(),
         }));
 
         // Create channel for manual refresh triggers
-        let (refresh_tx, refresh_rx) = mpsc::channel::<()>(10);
+        let (refresh_tx, refresh_rx) = mpsc::channel::<RefreshScope>(10);
 
         // Create client for background task
         let bg_client = create_client();
@@ -291,8 +714,9 @@ async fn main() {
         // Spawn background task to fetch data
         let shared_data_clone = Arc::clone(&shared_data);
         let refresh_interval = config.refresh_interval as u64;
+        let request_timeout = Duration::from_secs(config.request_timeout_secs);
         tokio::spawn(async move {
-            fetch_data_loop(bg_client, shared_data_clone, refresh_interval, refresh_rx).await;
+            fetch_data_loop(bg_client, shared_data_clone, refresh_interval, request_timeout, refresh_rx).await;
         });
 
         if let Err(e) = tui::run(shared_data, refresh_tx).await {
@@ -304,15 +728,31 @@ async fn main() {
 
     let command = cli.command.unwrap();
 
+    // Handle Completions command separately (doesn't need a client)
+    if let Commands::Completions { shell } = command {
+        write_completions(shell, &mut std::io::stdout());
+        return;
+    }
+
+    // Handle Man command separately (doesn't need a client)
+    if let Commands::Man = command {
+        if let Err(e) = write_man_page(&mut std::io::stdout()) {
+            eprintln!("Failed to render man page: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Handle Info command separately (doesn't need a client)
+    if let Commands::Info = command {
+        let (path_str, exists) = config_path_status();
+        print!("{}", format_info(&path_str, exists, &config));
+        return;
+    }
+
     // Handle Config command separately (doesn't need a client)
     if let Commands::Config = command {
-        let (path_str, exists) = match config::get_config_path() {
-            Some(path) => {
-                let exists = path.exists();
-                (path.display().to_string(), exists)
-            }
-            None => ("Unable to determine config path".to_string(), false),
-        };
+        let (path_str, exists) = config_path_status();
 
         println!("Configuration File: {} (Exists: {})", path_str, if exists { "yes" } else { "no" });
         println!();
@@ -323,30 +763,363 @@ async fn main() {
         println!("refresh_interval: {} seconds", config.refresh_interval);
         println!("display_standings_western_first: {}", config.display_standings_western_first);
         println!("time_format: {}", config.time_format);
+        println!("screenshot_dir: {}", config.screenshot_dir);
+        println!("screenshot_name_template: {}", config.screenshot_name_template);
+        println!("screenshot_format: {}", config.screenshot_format);
+        println!("show_ticker: {}", config.show_ticker);
+        println!("number_locale: {}", config.number_locale);
+        match config.default_season {
+            Some(season) => println!("default_season: {}", season),
+            None => println!("default_season: current"),
+        }
+        println!("followed_teams: {}", config.followed_teams.join(", "));
+        println!("content_indent: {}", config.content_indent);
+        println!("request_timeout_secs: {}", config.request_timeout_secs);
+        println!("focus_color: {}", config.focus_color);
+        println!("tabs: {}", config.tabs.join(", "));
+        println!("tab_nav_wrap: {}", config.tab_nav_wrap);
+        println!("restore_session: {}", config.restore_session);
+        println!("standings_stack_width: {}", config.standings_stack_width);
+        println!("separator_style: {}", config.separator_style);
+        println!("standings_columns: {}", config.standings_columns.join(", "));
         return;
     }
 
     // Create client once for all other commands
     let client = create_client();
+    let color = commands::use_color(cli.no_color);
+    let quiet = cli.quiet;
+    let separator_style = commands::formatting::SeparatorStyle::parse(&config.separator_style).unwrap_or_else(|| {
+        tracing::warn!("Unknown separator_style '{}', using default", config.separator_style);
+        commands::formatting::SeparatorStyle::default()
+    })
+    .for_terminal(commands::locale_is_utf8());
+    let table_format = commands::standings::TableFormat {
+        separator_style,
+        columns: commands::standings::parse_columns(&config.standings_columns),
+        selected: None,
+    };
 
     match command {
-        Commands::Config => unreachable!(), // Already handled above
-        Commands::Standings { season, date, by } => {
+        // Already handled above
+        Commands::Config | Commands::Info | Commands::Completions { .. } | Commands::Man => unreachable!(),
+        Commands::Standings { season, date, by, western_first, eastern_first, watch, interval } => {
+            if let Some(season) = season {
+                commands::validate_season(season).unwrap_or_else(|e| commands::exit_with_error(&e));
+            }
+            let season = season.or(config.default_season);
+            let western_first = if western_first || eastern_first {
+                western_first
+            } else {
+                config.display_standings_western_first
+            };
             let group_by = match by {
                 GroupBy::Division => commands::standings::GroupBy::Division,
                 GroupBy::Conference => commands::standings::GroupBy::Conference,
                 GroupBy::League => commands::standings::GroupBy::League,
             };
-            commands::standings::run(&client, season, date, group_by).await;
+            if watch {
+                let interval = resolve_watch_interval(interval, config.refresh_interval);
+                run_watch(interval, || commands::standings::run(&client, season, date.clone(), group_by, western_first, quiet, &table_format)).await;
+            } else {
+                commands::standings::run(&client, season, date, group_by, western_first, quiet, &table_format)
+                    .await
+                    .unwrap_or_else(|e| commands::exit_with_error(&e));
+            }
         }
-        Commands::Boxscore { game_id } => {
-            commands::boxscore::run(&client, game_id).await;
+        Commands::Boxscore { game_id, rates } => {
+            let locale = commands::formatting::NumberLocale::parse(&config.number_locale).unwrap_or_else(|| {
+                tracing::warn!("Unknown number_locale '{}', using en-US", config.number_locale);
+                commands::formatting::NumberLocale::default()
+            });
+            commands::boxscore::run(&client, game_id, quiet, locale, rates, color, separator_style).await;
         }
-        Commands::Schedule { date } => {
-            commands::schedule::run(&client, date).await;
+        Commands::Schedule { date, team, week } => {
+            let team = validate_team_flag(team);
+            match week {
+                Some(week) => commands::schedule::run_week(&client, week, date, team, color, quiet).await,
+                None => commands::schedule::run(&client, date, team, color, quiet).await,
+            }
         }
-        Commands::Scores { date } => {
-            commands::scores::run(&client, date).await;
+        Commands::Scores { date, team, watch, interval } => {
+            let team = validate_team_flag(team);
+            if watch {
+                let interval = resolve_watch_interval(interval, config.refresh_interval);
+                run_watch(interval, || commands::scores::run(&client, date.clone(), team.clone(), color, quiet)).await;
+            } else {
+                commands::scores::run(&client, date, team, color, quiet)
+                    .await
+                    .unwrap_or_else(|e| commands::exit_with_error(&e));
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Log output collected in memory
+    #[derive(Clone, Default)]
+    struct Captured(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Captured {
+        fn writer(&self) -> BoxMakeWriter {
+            let captured = self.clone();
+            BoxMakeWriter::new(move || captured.clone())
+        }
+
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    #[test]
+    fn refresh_scope_picks_the_fetches() {
+        assert!(RefreshScope::All.includes_standings() && RefreshScope::All.includes_schedule());
+        assert!(RefreshScope::Standings.includes_standings() && !RefreshScope::Standings.includes_schedule());
+        assert!(!RefreshScope::Schedule.includes_standings() && RefreshScope::Schedule.includes_schedule());
+    }
+
+    #[test]
+    fn rate_limiting_starts_a_cooldown() {
+        let mut shared = SharedData::default();
+        record_fetch_error(&mut shared, "standings", &DataError::Timeout);
+        assert!(shared.rate_limited_until.is_none());
+
+        record_fetch_error(&mut shared, "standings", &DataError::RateLimited("slow down".to_string()));
+        let remaining = data_error::cooldown_remaining(shared.rate_limited_until, Instant::now()).unwrap();
+        assert!(remaining > data_error::RATE_LIMIT_COOLDOWN - Duration::from_secs(5));
+        assert!(shared.error_message.is_some());
+    }
+
+    #[tokio::test]
+    async fn slow_fetches_time_out() {
+        let slow = async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            anyhow::Ok(1)
+        };
+        let result = timed_fetch("standings", "current", Duration::from_millis(10), slow).await;
+        assert_eq!(result, Err(DataError::Timeout));
+
+        let fast = async { anyhow::Ok(1) };
+        assert_eq!(timed_fetch("standings", "current", Duration::from_millis(10), fast).await, Ok(1));
+    }
+
+    #[test]
+    fn info_lists_version_source_config_and_season() {
+        let info = format_info("/tmp/nhl/config.toml", false, &config::Config::default());
+        assert!(info.starts_with(&format!("nhl {}\n", env!("CARGO_PKG_VERSION"))));
+        assert!(info.contains(&format!("Data source: {}\n", NHL_API_BASE_URL)));
+        assert!(info.contains("Configuration File: /tmp/nhl/config.toml (Exists: no)\n"));
+        assert!(info.contains(&format!("Current season: {}\n", commands::current_season())));
+
+        let info = format_info("/tmp/nhl/config.toml", true, &config::Config::default());
+        assert!(info.contains("(Exists: yes)"));
+    }
+
+    #[test]
+    fn bash_completions_cover_the_subcommands() {
+        let mut buffer = Vec::new();
+        write_completions(clap_complete::Shell::Bash, &mut buffer);
+        let script = String::from_utf8(buffer).unwrap();
+        assert!(script.contains("_nhl()"));
+        for subcommand in ["standings", "boxscore", "schedule", "scores", "config", "info", "completions", "man"] {
+            assert!(script.contains(&format!("nhl,{})", subcommand)), "missing {}", subcommand);
+        }
+    }
+
+    #[test]
+    fn man_page_has_a_versioned_header_and_the_subcommands() {
+        let mut buffer = Vec::new();
+        write_man_page(&mut buffer).unwrap();
+        let page = String::from_utf8(buffer).unwrap();
+        assert!(page.contains(&format!(".TH nhl 1  \"nhl {}\"", env!("CARGO_PKG_VERSION"))));
+        assert!(page.contains(".SH SUBCOMMANDS"));
+        for subcommand in ["standings", "boxscore", "schedule", "scores", "config", "info", "completions", "man"] {
+            assert!(page.contains(&format!("nhl\\-{}(1)", subcommand)), "missing {}", subcommand);
+        }
+    }
+
+    #[test]
+    fn watch_interval_prefers_the_flag_then_the_config() {
+        assert_eq!(resolve_watch_interval(Some(15), 30), 15);
+        assert_eq!(resolve_watch_interval(None, 30), 30);
+        assert_eq!(resolve_watch_interval(None, 0), DEFAULT_WATCH_INTERVAL);
+        // A zero interval would spin, so it falls through to the next source
+        assert_eq!(resolve_watch_interval(Some(0), 30), 30);
+        assert_eq!(resolve_watch_interval(Some(0), 0), DEFAULT_WATCH_INTERVAL);
+    }
+
+    #[test]
+    fn elapsed_ms_counts_from_the_start() {
+        let start = Instant::now() - Duration::from_millis(50);
+        assert!(elapsed_ms(start) >= 50);
+    }
+
+    #[tokio::test]
+    async fn fetches_log_their_span_and_duration() {
+        let captured = Captured::default();
+        let subscriber = FmtSubscriber::builder()
+            .with_max_level(Level::DEBUG)
+            .with_writer(captured.writer())
+            .with_ansi(false)
+            .json()
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let result = timed_fetch("standings", "current", Duration::from_secs(5), async { anyhow::Ok(1) }).await;
+        assert_eq!(result.unwrap(), 1);
+
+        let log = captured.text();
+        assert!(log.contains("\"message\":\"fetch completed\""));
+        assert!(log.contains("\"duration_ms\":"));
+        assert!(log.contains("\"success\":true"));
+        assert!(log.contains("\"name\":\"fetch\""));
+        assert!(log.contains("\"resource\":\"standings\""));
+        assert!(log.contains("\"key\":\"current\""));
+    }
+
+    #[test]
+    fn log_format_parses_json_and_text() {
+        let format = |args: &[&str]| Cli::try_parse_from([&["nhl"], args].concat()).unwrap().log_format;
+        assert!(matches!(format(&[]), LogFormat::Plain));
+        assert!(matches!(format(&["--log-format", "json"]), LogFormat::Json));
+        assert!(matches!(format(&["--log-format", "text"]), LogFormat::Plain));
+        assert!(matches!(format(&["--log-format", "plain"]), LogFormat::Plain));
+        assert!(Cli::try_parse_from(["nhl", "--log-format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn json_logs_are_one_object_per_line() {
+        let captured = Captured::default();
+        let subscriber = build_subscriber(build_log_filter(None, Level::INFO), captured.writer(), LogFormat::Json);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(resource = "standings", "fetch completed");
+            tracing::debug!("filtered out");
+        });
+
+        let log = captured.text();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let event: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(event["level"], "INFO");
+        assert_eq!(event["fields"]["message"], "fetch completed");
+        assert_eq!(event["fields"]["resource"], "standings");
+
+        let captured = Captured::default();
+        let subscriber = build_subscriber(build_log_filter(None, Level::INFO), captured.writer(), LogFormat::Plain);
+        tracing::subscriber::with_default(subscriber, || tracing::info!("fetch completed"));
+        let log = captured.text();
+        assert!(log.contains("INFO") && log.contains("fetch completed"));
+        assert!(serde_json::from_str::<serde_json::Value>(log.trim()).is_err());
+    }
+
+    #[test]
+    fn log_writer_follows_the_file_and_stderr_flags() {
+        use std::io::Write;
+        use tracing_subscriber::fmt::MakeWriter;
+
+        // Default: nowhere to write, so logging stays off
+        assert!(select_log_writer("/dev/null", false).is_none());
+        assert!(select_log_writer("/dev/null", true).is_some());
+
+        let path = std::env::temp_dir().join(format!("nhl-log-test-{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let writer = select_log_writer(path.to_str().unwrap(), false).unwrap();
+        writer.make_writer().write_all(b"logged\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "logged\n");
+        assert!(select_log_writer(path.to_str().unwrap(), true).is_some());
+        std::fs::remove_file(&path).unwrap();
+
+        // An unwritable file is skipped, leaving stderr if it was asked for
+        let unwritable = "/nonexistent-nhl-dir/nhl.log";
+        assert!(select_log_writer(unwritable, false).is_none());
+        assert!(select_log_writer(unwritable, true).is_some());
+    }
+
+    #[test]
+    fn log_filter_falls_back_to_the_level() {
+        let filter = build_log_filter(Some("nhl::commands=debug,info"), Level::WARN).to_string();
+        assert!(filter.contains("nhl::commands=debug"), "{}", filter);
+        assert!(!filter.contains("warn"), "{}", filter);
+
+        assert_eq!(build_log_filter(Some("nhl=loud"), Level::WARN).to_string(), "warn");
+        assert_eq!(build_log_filter(None, Level::DEBUG).to_string(), "debug");
+    }
+
+    #[test]
+    fn schedules_from_an_old_epoch_are_discarded() {
+        let mut shared = SharedData { fetch_epoch: 2, ..SharedData::default() };
+        let schedule = commands::test_support::schedule(Vec::new());
+
+        assert!(!store_schedule(&mut shared, 1, "2024-11-02", schedule.clone()));
+        assert!(shared.schedule.is_none());
+        assert!(shared.schedule_cache.is_empty());
+        assert_eq!(shared.data_version, 0);
+
+        assert!(store_schedule(&mut shared, 2, "2024-11-02", schedule));
+        assert!(shared.schedule.is_some());
+        assert!(shared.schedule_cache.contains_key("2024-11-02"));
+        assert_eq!(shared.data_version, 1);
+    }
+
+    #[tokio::test]
+    async fn game_fetches_are_capped() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = AtomicUsize::new(0);
+        let most_in_flight = AtomicUsize::new(0);
+        let results: Vec<usize> = fetch_concurrently(0..10, |id| {
+            let (in_flight, most_in_flight) = (&in_flight, &most_in_flight);
+            async move {
+                let running = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                most_in_flight.fetch_max(running, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                id
+            }
+        })
+        .collect()
+        .await;
+
+        assert_eq!(results.len(), 10);
+        assert_eq!(most_in_flight.load(Ordering::SeqCst), MAX_CONCURRENT_GAME_FETCHES);
+    }
+
+    #[tokio::test]
+    async fn prefetch_fills_the_neighbouring_days_once() {
+        use std::sync::Mutex;
+
+        let day = nhl_api::GameDate::Date(chrono::NaiveDate::from_ymd_opt(2024, 11, 2).unwrap());
+        let shared_data: SharedDataHandle = Arc::new(RwLock::new(SharedData::default()));
+        shared_data.write().await.schedule_cache.insert("2024-10-25".to_string(), commands::test_support::schedule(Vec::new()));
+        let fetched = Mutex::new(Vec::new());
+        let fetch = |date: nhl_api::GameDate| {
+            fetched.lock().unwrap().push(game_date_key(&date));
+            async { anyhow::Ok(commands::test_support::schedule(Vec::new())) }
+        };
+
+        prefetch_adjacent_schedules(&fetch, &shared_data, &day, 0, Duration::from_secs(5)).await;
+        let mut cached: Vec<String> = shared_data.read().await.schedule_cache.keys().cloned().collect();
+        cached.sort();
+        // Dates further out are dropped
+        assert_eq!(cached, ["2024-11-01", "2024-11-03"]);
+        assert_eq!(*fetched.lock().unwrap(), ["2024-11-01", "2024-11-03"]);
+
+        // Both neighbours are cached now, so the next refresh fetches nothing
+        prefetch_adjacent_schedules(&fetch, &shared_data, &day, 0, Duration::from_secs(5)).await;
+        assert_eq!(fetched.lock().unwrap().len(), 2);
+    }
+}
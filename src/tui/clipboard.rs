@@ -0,0 +1,86 @@
+use std::io::{self, Write};
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard base64 with padding, as OSC 52 expects
+fn base64_encode(data: &[u8]) -> String {
+    let mut output = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let triple = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(BASE64_ALPHABET[(triple >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+/// Tab content as copied: without the left margin, trailing spaces or trailing blank lines
+pub fn content_text(content: &str, indent: usize) -> String {
+    let indent = " ".repeat(indent);
+    let text = content
+        .lines()
+        .map(|line| line.strip_prefix(indent.as_str()).unwrap_or(line).trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+    text.trim_end().to_string()
+}
+
+/// OSC 52 sequence setting the clipboard to `text`
+fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
+/// Whether the terminal can be expected to handle OSC 52; the Linux console
+/// and dumb terminals ignore it, so copying there would silently do nothing
+fn terminal_supports_clipboard() -> bool {
+    match std::env::var("TERM") {
+        Ok(term) => term != "linux" && term != "dumb",
+        Err(_) => false,
+    }
+}
+
+/// Put `text` on the system clipboard through the terminal (OSC 52), which also
+/// works over SSH without any clipboard tool installed
+pub fn copy(text: &str) -> io::Result<()> {
+    if !terminal_supports_clipboard() {
+        return Err(io::Error::other("this terminal has no clipboard support"));
+    }
+    let mut stdout = io::stdout();
+    write!(stdout, "{}", osc52_sequence(text))?;
+    stdout.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_matches_the_rfc_4648_vectors() {
+        // One, two and no padding characters
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (input, encoded) in vectors {
+            assert_eq!(base64_encode(input.as_bytes()), encoded, "{:?}", input);
+        }
+        assert_eq!(base64_encode(&[0xfb, 0xff]), "+/8=");
+    }
+
+    #[test]
+    fn content_is_copied_without_the_margin() {
+        let content = "  Atlantic\n  ════════   \n\n  BOS   19  \n   indented\nno margin\n  \n";
+        assert_eq!(content_text(content, 2), "Atlantic\n════════\n\nBOS   19\n indented\nno margin");
+        assert_eq!(osc52_sequence("BOS"), "\x1b]52;c;Qk9T\x07");
+    }
+}
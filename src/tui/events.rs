@@ -1,51 +1,240 @@
-use crossterm::event::{KeyCode, KeyEvent};
-use crate::commands::standings::GroupBy;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use crate::commands::standings::{toggle_column, GroupBy, SortColumn};
 use super::tabs::{AppState, Tab};
 use crate::SharedDataHandle;
-use tokio::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// Quiet time after the last date change before the new date is fetched,
+/// so holding Left/Right doesn't fetch every date skipped over
+const DATE_NAVIGATION_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Largest count accepted before a date navigation key (e.g. "7" then Right)
+const MAX_COUNT: u32 = 99;
 
 pub enum AppAction {
     Continue,
     Exit,
+    Screenshot,
+    /// Refresh the data behind the current tab
+    Refresh,
+    /// Copy the current tab's content to the clipboard
+    Copy,
+}
+
+/// Move the scores date by `days`; the refresh is sent by the main loop once navigation settles.
+/// Per-game details are kept: the next schedule load evicts only the games it no longer lists.
+async fn change_game_date(state: &mut AppState, shared_data: &SharedDataHandle, days: i64) {
+    {
+        let mut data = shared_data.write().await;
+        data.game_date = data.game_date.add_days(days);
+        // Show the prefetched schedule if there is one, "Loading..." otherwise
+        data.schedule = data.schedule_cache.get(&crate::game_date_key(&data.game_date)).cloned();
+        data.data_version += 1;
+        // Results still in flight for the previous date are now stale
+        data.fetch_epoch += 1;
+    }
+    state.date_changed_at = Some(Instant::now());
+}
+
+/// Whether date navigation has been idle long enough to fetch the selected date
+pub fn date_navigation_settled(changed_at: Instant, now: Instant) -> bool {
+    now.duration_since(changed_at) >= DATE_NAVIGATION_DEBOUNCE
 }
 
-pub async fn handle_key_event(key: KeyEvent, state: &mut AppState, shared_data: &SharedDataHandle, refresh_tx: &mpsc::Sender<()>) -> AppAction {
+/// Whether the standings header row has focus, so Left/Right pick a column and Enter sorts by it
+fn header_focused(state: &AppState) -> bool {
+    state.current_tab == Tab::Standings && state.subtab_focused && state.standings_header.is_some()
+}
+
+/// Sort the standings by `column`, flipping the order if they're already sorted by it
+fn sort_standings_by(state: &mut AppState, column: SortColumn) {
+    let sort = &mut state.standings_sort;
+    if sort.column == column {
+        sort.descending = !sort.descending;
+    } else {
+        sort.column = column;
+        sort.descending = true;
+    }
+    state.status_messages.push_info(format!(
+        "Sorted by {} ({})",
+        sort.column.name(),
+        if sort.descending { "descending" } else { "ascending" }
+    ));
+}
+
+/// Handle a key while the ':' tab prompt is open: typing filters, Enter jumps, Esc cancels
+fn handle_tab_jump_key(key: KeyEvent, state: &mut AppState, mut input: String) {
     match key.code {
-        KeyCode::Esc => AppAction::Exit,
+        KeyCode::Enter => {
+            match Tab::matching(&input, &state.tabs) {
+                Some(tab) => {
+                    state.current_tab = tab;
+                    state.subtab_focused = false;
+                }
+                None => state.status_messages.push_error(format!("No tab matches '{}'", input)),
+            }
+            return;
+        }
+        KeyCode::Esc => return,
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Char(c) => input.push(c),
+        _ => {}
+    }
+    state.tab_jump = Some(input);
+}
+
+/// Switch tabs when a tab name is clicked; the tab bar sits on the top row of the screen
+pub fn handle_mouse_event(mouse: MouseEvent, state: &mut AppState) {
+    if mouse.kind != MouseEventKind::Down(MouseButton::Left) || mouse.row != 0 {
+        return;
+    }
+    if let Some(tab) = super::widgets::tab_at(&state.tabs, mouse.column) {
+        state.current_tab = tab;
+        state.subtab_focused = false;
+    }
+}
+
+pub async fn handle_key_event(key: KeyEvent, state: &mut AppState, shared_data: &SharedDataHandle) -> AppAction {
+    // Any key press dismisses the queued info messages
+    state.status_messages.clear_info();
+
+    // Force quit: exits no matter what is in progress
+    let ctrl_c = key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+    if ctrl_c || key.code == KeyCode::Char('Q') {
+        return AppAction::Exit;
+    }
+
+    // The tab prompt takes every key until it's closed
+    if let Some(input) = state.tab_jump.take() {
+        handle_tab_jump_key(key, state, input);
+        return AppAction::Continue;
+    }
+
+    // Digits build a count for the next date navigation or column key, vim-style
+    if let KeyCode::Char(digit @ '0'..='9') = key.code {
+        if digit != '0' || state.count.is_some() {
+            let value = state.count.unwrap_or(0) * 10 + digit.to_digit(10).unwrap_or(0);
+            state.count = Some(value.min(MAX_COUNT));
+            return AppAction::Continue;
+        }
+    }
+    // Any other key uses up the count
+    let count = state.count.take();
+    let days = count.unwrap_or(1) as usize;
+
+    match key.code {
+        // Esc first cancels a pending count
+        KeyCode::Esc if count.is_some() => AppAction::Continue,
+
+        // Safe quit: only reached when no prompt is open
+        KeyCode::Esc | KeyCode::Char('q') => AppAction::Exit,
+
+        // Open the prompt to jump to a tab by name
+        KeyCode::Char(':') => {
+            state.tab_jump = Some(String::new());
+            AppAction::Continue
+        }
+
+        // Save the current screen to a text file
+        KeyCode::Char('S') => AppAction::Screenshot,
+
+        // Copy the current tab's content as text
+        KeyCode::Char('Y') => AppAction::Copy,
+
+        // Refetch only what the current tab shows
+        KeyCode::Char('r') => AppAction::Refresh,
+
+        // Sort the standings by the next column, or flip the order
+        KeyCode::Char('s') | KeyCode::Char('o') if state.current_tab == Tab::Standings => {
+            let column = if key.code == KeyCode::Char('s') {
+                state.standings_sort.column.next_shown(&state.standings_columns)
+            } else {
+                state.standings_sort.column
+            };
+            sort_standings_by(state, column);
+            AppAction::Continue
+        }
+
+        // Sort by the column selected in the header row
+        KeyCode::Enter if header_focused(state) => {
+            if let Some(column) = state.standings_header {
+                sort_standings_by(state, column);
+            }
+            AppAction::Continue
+        }
+
+        // Show or hide the count-th standings column; without a count, show them all
+        KeyCode::Char('c') if state.current_tab == Tab::Standings => {
+            let all = SortColumn::all();
+            match count {
+                Some(n) if (1..=all.len()).contains(&(n as usize)) => {
+                    if !toggle_column(&mut state.standings_columns, all[n as usize - 1]) {
+                        state.status_messages.push_info("The last standings column can't be hidden".to_string());
+                        return AppAction::Continue;
+                    }
+                }
+                Some(n) => {
+                    state.status_messages.push_info(format!("No standings column {}", n));
+                    return AppAction::Continue;
+                }
+                None => state.standings_columns = all.to_vec(),
+            }
+            // Don't keep sorting by, or selecting, a column that's no longer shown
+            let sort = &mut state.standings_sort;
+            if !state.standings_columns.contains(&sort.column) {
+                sort.column = sort.column.next_shown(&state.standings_columns);
+                sort.descending = true;
+            }
+            if let Some(selected) = state.standings_header.filter(|selected| !state.standings_columns.contains(selected)) {
+                state.standings_header = Some(selected.next_shown(&state.standings_columns));
+            }
+            let names: Vec<&str> = state.standings_columns.iter().map(|column| column.name()).collect();
+            state.status_messages.push_info(format!("Columns: {}", names.join(" ")));
+            AppAction::Continue
+        }
+
+        // Toggle showing only the followed teams
+        KeyCode::Char('f') => {
+            let followed_teams = shared_data.read().await.config.followed_teams.clone();
+            if followed_teams.is_empty() {
+                state.status_messages.push_info("No followed_teams configured".to_string());
+            } else {
+                state.following_only = !state.following_only;
+                state.status_messages.push_info(if state.following_only {
+                    format!("Following: {}", followed_teams.join(", "))
+                } else {
+                    "Showing all teams".to_string()
+                });
+            }
+            AppAction::Continue
+        }
+
+        // Move the header selection, stopping at the first and last shown columns
+        KeyCode::Left | KeyCode::Right if header_focused(state) => {
+            let columns = &state.standings_columns;
+            let index = state.standings_header
+                .and_then(|selected| columns.iter().position(|column| *column == selected))
+                .unwrap_or(0);
+            let index = if key.code == KeyCode::Left {
+                index.saturating_sub(days)
+            } else {
+                (index + days).min(columns.len() - 1)
+            };
+            state.standings_header = Some(columns[index]);
+            AppAction::Continue
+        }
 
         // Arrow key navigation
         KeyCode::Left => {
             if state.subtab_focused {
                 if state.current_tab == Tab::Scores {
-                    // Navigate scores dates - move selection left
-                    if state.scores_selected_index > 0 {
-                        // Move selection within visible window
-                        state.scores_selected_index -= 1;
-                        // Update game_date to the newly selected date
-                        {
-                            let mut data = shared_data.write().await;
-                            data.game_date = data.game_date.add_days(-1);
-                            // Clear schedule data to show "Loading..." while fetching
-                            data.schedule = None;
-                            data.period_scores.clear();
-                            data.game_info.clear();
-                        }
-                        // Trigger immediate refresh
-                        let _ = refresh_tx.send(()).await;
-                    } else {
-                        // Already at leftmost position, shift window left
-                        {
-                            let mut data = shared_data.write().await;
-                            data.game_date = data.game_date.add_days(-1);
-                            // Clear schedule data to show "Loading..." while fetching
-                            data.schedule = None;
-                            data.period_scores.clear();
-                            data.game_info.clear();
-                        }
-                        // Trigger immediate refresh
-                        let _ = refresh_tx.send(()).await;
-                        // Keep selection at index 0 (leftmost)
-                    }
+                    // Navigate scores dates - move selection left, shifting the
+                    // window once the selection reaches the leftmost position
+                    state.scores_selected_index = state.scores_selected_index.saturating_sub(days);
+                    // Update game_date to the newly selected date
+                    change_game_date(state, shared_data, -(days as i64)).await;
                 } else if state.current_tab == Tab::Standings {
                     // Navigate standings view
                     state.standings_view = match state.standings_view {
@@ -56,11 +245,7 @@ pub async fn handle_key_event(key: KeyEvent, state: &mut AppState, shared_data:
                 }
             } else {
                 // Navigate main tabs
-                state.current_tab = match state.current_tab {
-                    Tab::Scores => Tab::Settings,
-                    Tab::Standings => Tab::Scores,
-                    Tab::Settings => Tab::Standings,
-                };
+                state.current_tab = state.current_tab.prev(&state.tabs, state.tab_nav_wrap);
                 // Reset subtab focus when leaving tabs with subtabs
                 if state.current_tab != Tab::Standings && state.current_tab != Tab::Scores {
                     state.subtab_focused = false;
@@ -71,35 +256,11 @@ pub async fn handle_key_event(key: KeyEvent, state: &mut AppState, shared_data:
         KeyCode::Right => {
             if state.subtab_focused {
                 if state.current_tab == Tab::Scores {
-                    // Navigate scores dates - move selection right
-                    if state.scores_selected_index < 2 {
-                        // Move selection within visible window
-                        state.scores_selected_index += 1;
-                        // Update game_date to the newly selected date
-                        {
-                            let mut data = shared_data.write().await;
-                            data.game_date = data.game_date.add_days(1);
-                            // Clear schedule data to show "Loading..." while fetching
-                            data.schedule = None;
-                            data.period_scores.clear();
-                            data.game_info.clear();
-                        }
-                        // Trigger immediate refresh
-                        let _ = refresh_tx.send(()).await;
-                    } else {
-                        // Already at rightmost position, shift window right
-                        {
-                            let mut data = shared_data.write().await;
-                            data.game_date = data.game_date.add_days(1);
-                            // Clear schedule data to show "Loading..." while fetching
-                            data.schedule = None;
-                            data.period_scores.clear();
-                            data.game_info.clear();
-                        }
-                        // Trigger immediate refresh
-                        let _ = refresh_tx.send(()).await;
-                        // Keep selection at index 2 (rightmost)
-                    }
+                    // Navigate scores dates - move selection right, shifting the
+                    // window once the selection reaches the rightmost position
+                    state.scores_selected_index = (state.scores_selected_index + days).min(2);
+                    // Update game_date to the newly selected date
+                    change_game_date(state, shared_data, days as i64).await;
                 } else if state.current_tab == Tab::Standings {
                     // Navigate standings view
                     state.standings_view = match state.standings_view {
@@ -110,11 +271,7 @@ pub async fn handle_key_event(key: KeyEvent, state: &mut AppState, shared_data:
                 }
             } else {
                 // Navigate main tabs
-                state.current_tab = match state.current_tab {
-                    Tab::Scores => Tab::Standings,
-                    Tab::Standings => Tab::Settings,
-                    Tab::Settings => Tab::Scores,
-                };
+                state.current_tab = state.current_tab.next(&state.tabs, state.tab_nav_wrap);
                 // Reset subtab focus when leaving tabs with subtabs
                 if state.current_tab != Tab::Standings && state.current_tab != Tab::Scores {
                     state.subtab_focused = false;
@@ -126,12 +283,18 @@ pub async fn handle_key_event(key: KeyEvent, state: &mut AppState, shared_data:
             // Activate sub-tab navigation (on Scores or Standings tabs)
             if (state.current_tab == Tab::Scores || state.current_tab == Tab::Standings) && !state.subtab_focused {
                 state.subtab_focused = true;
+                state.standings_header = None;
+            } else if state.current_tab == Tab::Standings && state.subtab_focused && state.standings_header.is_none() {
+                // From the standings views into the header row, starting on the sort column
+                state.standings_header = Some(state.standings_sort.column);
             }
             AppAction::Continue
         }
         KeyCode::Up => {
-            // Deactivate sub-tab navigation
-            if state.subtab_focused {
+            // Leave the header row, then deactivate sub-tab navigation
+            if header_focused(state) {
+                state.standings_header = None;
+            } else if state.subtab_focused {
                 state.subtab_focused = false;
             }
             AppAction::Continue
@@ -140,3 +303,158 @@ pub async fn handle_key_event(key: KeyEvent, state: &mut AppState, shared_data:
         _ => AppAction::Continue,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    fn shared_data() -> SharedDataHandle {
+        Arc::new(RwLock::new(crate::SharedData::default()))
+    }
+
+    async fn press(state: &mut AppState, shared_data: &SharedDataHandle, codes: &[KeyCode]) {
+        for code in codes {
+            handle_key_event(KeyEvent::new(*code, KeyModifiers::NONE), state, shared_data).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn enter_sorts_by_the_selected_header_column() {
+        let shared_data = shared_data();
+        let mut state = AppState { current_tab: Tab::Standings, ..AppState::default() };
+
+        // Down to the views, Down into the header on the sort column (PTS)
+        press(&mut state, &shared_data, &[KeyCode::Down, KeyCode::Down]).await;
+        assert_eq!(state.standings_header, Some(SortColumn::Points));
+
+        // PTS -> OT -> L -> W, then sort by it
+        press(&mut state, &shared_data, &[KeyCode::Left, KeyCode::Left, KeyCode::Left, KeyCode::Enter]).await;
+        assert_eq!(state.standings_sort.column, SortColumn::Wins);
+        assert!(state.standings_sort.descending);
+
+        // Enter again flips the order
+        press(&mut state, &shared_data, &[KeyCode::Enter]).await;
+        assert_eq!(state.standings_sort.column, SortColumn::Wins);
+        assert!(!state.standings_sort.descending);
+
+        // The selection stops at both ends
+        press(&mut state, &shared_data, &[KeyCode::Left, KeyCode::Left, KeyCode::Left]).await;
+        assert_eq!(state.standings_header, Some(SortColumn::GamesPlayed));
+        press(&mut state, &shared_data, &[KeyCode::Char('9'), KeyCode::Right]).await;
+        assert_eq!(state.standings_header, Some(SortColumn::Points));
+
+        // Up leaves the header, then the views; the view stayed put throughout
+        press(&mut state, &shared_data, &[KeyCode::Up]).await;
+        assert_eq!(state.standings_header, None);
+        assert!(state.subtab_focused);
+        assert_eq!(state.standings_view, GroupBy::Division);
+        press(&mut state, &shared_data, &[KeyCode::Up]).await;
+        assert!(!state.subtab_focused);
+    }
+
+    #[test]
+    fn date_navigation_settles_after_the_debounce() {
+        let changed_at = Instant::now();
+        assert!(!date_navigation_settled(changed_at, changed_at));
+        assert!(!date_navigation_settled(changed_at, changed_at + DATE_NAVIGATION_DEBOUNCE - Duration::from_millis(1)));
+        assert!(date_navigation_settled(changed_at, changed_at + DATE_NAVIGATION_DEBOUNCE));
+        assert!(date_navigation_settled(changed_at, changed_at + DATE_NAVIGATION_DEBOUNCE + Duration::from_millis(1)));
+    }
+
+    fn period_scores() -> crate::commands::scores_format::PeriodScores {
+        crate::commands::scores_format::PeriodScores {
+            away_periods: vec![1, 0, 2],
+            home_periods: vec![0, 1, 0],
+            has_ot: false,
+            has_so: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn games_on_both_dates_keep_their_details() {
+        use crate::commands::test_support::{game, schedule};
+        use nhl_api::GameState;
+
+        let shared_data = shared_data();
+        {
+            let mut data = shared_data.write().await;
+            data.game_date = nhl_api::GameDate::Date(chrono::NaiveDate::from_ymd_opt(2024, 11, 2).unwrap());
+            data.period_scores.insert(1, period_scores());
+            data.period_scores.insert(2, period_scores());
+        }
+        let mut state = AppState::default();
+
+        change_game_date(&mut state, &shared_data, 1).await;
+        let epoch = {
+            let data = shared_data.read().await;
+            // Nothing is evicted until the new date's schedule arrives
+            assert_eq!(data.period_scores.len(), 2);
+            assert_eq!(crate::game_date_key(&data.game_date), "2024-11-03");
+            data.fetch_epoch
+        };
+        assert!(state.date_changed_at.is_some());
+
+        // Game 1 is listed on the new date too (e.g. it runs past midnight); game 2 isn't
+        let next_day = schedule(vec![game(1, "BOS", "TOR", GameState::Live), game(3, "MTL", "OTT", GameState::Future)]);
+        assert!(crate::store_schedule(&mut *shared_data.write().await, epoch, "2024-11-03", next_day));
+        let data = shared_data.read().await;
+        assert!(data.period_scores.contains_key(&1));
+        assert!(!data.period_scores.contains_key(&2));
+    }
+
+    #[tokio::test]
+    async fn force_quit_works_while_the_prompt_is_open() {
+        let shared_data = shared_data();
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        let key = |code: KeyCode| KeyEvent::new(code, KeyModifiers::NONE);
+
+        for quit in [key(KeyCode::Char('Q')), ctrl_c] {
+            let mut state = AppState { tab_jump: Some("sta".to_string()), ..AppState::default() };
+            assert!(matches!(handle_key_event(quit, &mut state, &shared_data).await, AppAction::Exit));
+        }
+
+        // q is typed into the prompt and Esc closes it; neither quits
+        let mut state = AppState { tab_jump: Some(String::new()), ..AppState::default() };
+        assert!(matches!(handle_key_event(key(KeyCode::Char('q')), &mut state, &shared_data).await, AppAction::Continue));
+        assert_eq!(state.tab_jump.as_deref(), Some("q"));
+        assert!(matches!(handle_key_event(key(KeyCode::Esc), &mut state, &shared_data).await, AppAction::Continue));
+        assert_eq!(state.tab_jump, None);
+    }
+
+    #[tokio::test]
+    async fn counts_repeat_the_next_move_then_reset() {
+        let shared_data = shared_data();
+        shared_data.write().await.game_date = nhl_api::GameDate::Date(chrono::NaiveDate::from_ymd_opt(2024, 11, 2).unwrap());
+        let mut state = AppState { subtab_focused: true, scores_selected_index: 0, ..AppState::default() };
+        let game_date = || async { crate::game_date_key(&shared_data.read().await.game_date) };
+
+        // The dates are the rows of the scores tab: 3 then Right moves three days
+        press(&mut state, &shared_data, &[KeyCode::Char('3')]).await;
+        assert_eq!(state.count, Some(3));
+        press(&mut state, &shared_data, &[KeyCode::Right]).await;
+        assert_eq!(game_date().await, "2024-11-05");
+        assert_eq!(state.count, None);
+        assert_eq!(state.scores_selected_index, 2);
+
+        // The count is used up: the next move is a single day
+        press(&mut state, &shared_data, &[KeyCode::Left]).await;
+        assert_eq!(game_date().await, "2024-11-04");
+
+        // A leading 0 isn't a count, and counts stop at 99
+        press(&mut state, &shared_data, &[KeyCode::Char('0')]).await;
+        assert_eq!(state.count, None);
+        press(&mut state, &shared_data, &[KeyCode::Char('1'), KeyCode::Char('2'), KeyCode::Char('3')]).await;
+        assert_eq!(state.count, Some(MAX_COUNT));
+        press(&mut state, &shared_data, &[KeyCode::Char('5')]).await;
+        assert_eq!(state.count, Some(MAX_COUNT));
+
+        // Esc drops a pending count without quitting
+        assert!(matches!(
+            handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE), &mut state, &shared_data).await,
+            AppAction::Continue
+        ));
+        assert_eq!(state.count, None);
+    }
+}
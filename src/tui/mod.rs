@@ -1,6 +1,11 @@
 mod tabs;
 mod widgets;
 mod events;
+mod screenshot;
+mod status;
+mod ticker;
+mod session;
+mod clipboard;
 
 use std::io;
 use crossterm::{
@@ -13,13 +18,37 @@ use ratatui::{
     layout::{Constraint, Direction, Layout},
     Terminal,
 };
-use crate::SharedDataHandle;
+use crate::{RefreshScope, SharedDataHandle};
 use tabs::{AppState, Tab};
-use widgets::{render_tab_bar, render_standings_subtabs, render_scores_subtabs, render_status_bar, render_content};
-use events::{handle_key_event, AppAction};
+use crate::commands::standings::{StandingsGroups, StandingsSort};
+use widgets::{render_tab_bar, render_standings_subtabs, render_scores_subtabs, render_status_bar, build_content, render_content};
+use events::{handle_key_event, handle_mouse_event, AppAction};
 use tokio::sync::mpsc;
 
-pub async fn run(shared_data: SharedDataHandle, refresh_tx: mpsc::Sender<()>) -> Result<(), io::Error> {
+/// Everything the tab content depends on; the content is rebuilt only when this changes
+#[derive(Debug, Clone, PartialEq)]
+struct ContentKey {
+    tab: Tab,
+    standings_view: crate::commands::standings::GroupBy,
+    standings_sort: StandingsSort,
+    standings_columns: Vec<crate::commands::standings::SortColumn>,
+    standings_header: Option<crate::commands::standings::SortColumn>,
+    western_first: bool,
+    following_only: bool,
+    width: u16,
+    content_indent: usize,
+    data_version: u64,
+}
+
+/// The cached content if it was built for `key`, otherwise a fresh `build` that replaces it
+fn cached_content(cache: &mut Option<(ContentKey, String)>, key: ContentKey, build: impl FnOnce() -> String) -> &str {
+    if cache.as_ref().is_none_or(|(cached_key, _)| *cached_key != key) {
+        *cache = Some((key, build()));
+    }
+    cache.as_ref().map_or("", |(_, content)| content.as_str())
+}
+
+pub async fn run(shared_data: SharedDataHandle, refresh_tx: mpsc::Sender<RefreshScope>) -> Result<(), io::Error> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -28,31 +57,136 @@ pub async fn run(shared_data: SharedDataHandle, refresh_tx: mpsc::Sender<()>) ->
     let mut terminal = Terminal::new(backend)?;
 
     let mut app_state = AppState::default();
+    // Tab bar settings: the configured tabs (all of them if none are valid) and wrapping
+    {
+        let config = &shared_data.read().await.config;
+        app_state.tab_nav_wrap = config.tab_nav_wrap;
+        app_state.standings_columns = crate::commands::standings::parse_columns(&config.standings_columns);
+        let mut tabs = Vec::new();
+        for name in &config.tabs {
+            match Tab::parse(name) {
+                Some(tab) if !tabs.contains(&tab) => tabs.push(tab),
+                Some(_) => {}
+                None => app_state.status_messages.push_error(format!("Ignoring unknown tab '{}' in tabs", name)),
+            }
+        }
+        if !tabs.is_empty() {
+            app_state.current_tab = tabs[0];
+            app_state.tabs = tabs;
+        }
+    }
+    let restore_session = shared_data.read().await.config.restore_session;
+    if restore_session {
+        if let Some(session) = session::load() {
+            restore(&session, &mut app_state, &shared_data).await;
+        }
+    }
+    let unicode = crate::commands::locale_is_utf8();
+    let mut screenshot_requested = false;
+    let mut copy_requested = false;
+    let mut ticker_refresh = None;
+    let mut content_cache: Option<(ContentKey, String)> = None;
+    let mut standings_groups: Option<((u64, bool, StandingsSort), StandingsGroups)> = None;
 
     // Main loop
     loop {
-        // Read data from shared state
-        let (standings_data, schedule_data, period_scores_data, game_info_data, western_first, last_refresh, time_format, game_date, error_message) = {
-            let data = shared_data.read().await;
-            (
-                data.standings.clone(),
-                data.schedule.clone(),
-                data.period_scores.clone(),
-                data.game_info.clone(),
-                data.config.display_standings_western_first,
-                data.last_refresh,
-                data.config.time_format.clone(),
-                data.game_date.clone(),
-                data.error_message.clone(),
-            )
+        let now = std::time::Instant::now();
+        app_state.status_messages.expire(now);
+
+        // Fetch the selected date once date navigation has settled
+        if app_state.date_changed_at.is_some_and(|changed_at| events::date_navigation_settled(changed_at, now)) {
+            app_state.date_changed_at = None;
+            let _ = refresh_tx.send(RefreshScope::Schedule).await;
+        }
+
+        // Draw straight from the shared data instead of cloning it every frame;
+        // the read lock is held until the frame (and any screenshot) is done
+        let data = shared_data.read().await;
+        let config = &data.config;
+        let western_first = config.display_standings_western_first;
+        let last_refresh = data.last_refresh;
+        let refresh_interval = std::time::Duration::from_secs(config.refresh_interval as u64);
+        let time_format = &config.time_format;
+        let game_date = &data.game_date;
+        // While rate-limited, the cooldown replaces whatever error caused it
+        let cooldown_message = crate::data_error::cooldown_remaining(data.rate_limited_until, now)
+            .map(|remaining| format!("Rate limited by the NHL API, retrying in {}s", remaining.as_secs() + 1));
+        let error_message = cooldown_message.as_ref().or(data.error_message.as_ref());
+        let show_ticker = config.show_ticker;
+        // Anything that isn't a color (e.g. "none") leaves the focused bar uncolored
+        let focus_color = config.focus_color.parse::<ratatui::style::Color>().ok();
+        let content_indent = config.content_indent;
+        let data_version = data.data_version;
+        let period_scores_data = &data.period_scores;
+        let game_info_data = &data.game_info;
+
+        // Only the following filter needs a copy of the schedule
+        let followed_schedule;
+        let schedule_data = if app_state.following_only {
+            followed_schedule = data.schedule.clone().map(|mut schedule| {
+                crate::commands::filter_schedule_by_teams(&mut schedule, &config.followed_teams);
+                schedule
+            });
+            &followed_schedule
+        } else {
+            &data.schedule
         };
 
-        terminal.draw(|f| {
+        // Group the standings once per data, filter or sort change rather than on every rebuild
+        let groups_key = (data_version, app_state.following_only, app_state.standings_sort);
+        if standings_groups.as_ref().is_some_and(|(key, _)| *key != groups_key) {
+            standings_groups = None;
+        }
+        let (_, standings_groups_data) = standings_groups.get_or_insert_with(|| {
+            let groups = if app_state.following_only {
+                StandingsGroups::sorted(
+                    &crate::commands::filter_standings_by_teams(&data.standings, &config.followed_teams),
+                    app_state.standings_sort,
+                )
+            } else {
+                StandingsGroups::sorted(&data.standings, app_state.standings_sort)
+            };
+            (groups_key, groups)
+        });
+
+        let ticker_items = match (&schedule_data, show_ticker) {
+            (Some(schedule), true) => ticker::ticker_items(schedule, game_info_data),
+            _ => Vec::new(),
+        };
+        // Advance the ticker once per data refresh
+        if ticker_refresh != last_refresh {
+            if ticker_refresh.is_some() {
+                app_state.ticker_offset = ticker::advance_offset(app_state.ticker_offset, ticker_items.len());
+            }
+            ticker_refresh = last_refresh;
+        }
+
+        // The open tab prompt or pending count shows in place of the status messages
+        let prompt_text = match (&app_state.tab_jump, app_state.count) {
+            (Some(input), _) => Some(match Tab::matching(input, &app_state.tabs) {
+                Some(tab) => format!(":{} → {}", input, tab.name()),
+                None => format!(":{}", input),
+            }),
+            (None, Some(count)) => Some(count.to_string()),
+            (None, None) => None,
+        };
+        let prompt = prompt_text.map(|text| status::StatusMessage {
+            text,
+            is_error: false,
+            created: now,
+        });
+
+        // The header selection only shows while the standings sub-tabs have focus
+        let standings_header = app_state.standings_header
+            .filter(|_| app_state.subtab_focused && app_state.current_tab == Tab::Standings);
+
+        let completed_frame = terminal.draw(|f| {
             let size = f.area();
 
             // Create main layout - add space for sub-tabs if on Scores or Standings, and status bar at bottom
             let has_subtabs = app_state.current_tab == Tab::Scores || app_state.current_tab == Tab::Standings;
-            let constraints = if has_subtabs {
+            let has_ticker = show_ticker && app_state.current_tab == Tab::Scores;
+            let mut constraints = if has_subtabs {
                 vec![
                     Constraint::Length(2), // Main tab bar
                     Constraint::Length(2), // Sub-tab bar
@@ -66,6 +200,10 @@ pub async fn run(shared_data: SharedDataHandle, refresh_tx: mpsc::Sender<()>) ->
                     Constraint::Length(1), // Status bar
                 ]
             };
+            if has_ticker {
+                // Ticker sits right above the status bar
+                constraints.insert(constraints.len() - 1, Constraint::Length(1));
+            }
 
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -73,43 +211,119 @@ pub async fn run(shared_data: SharedDataHandle, refresh_tx: mpsc::Sender<()>) ->
                 .split(size);
 
             // Render main tab bar
-            render_tab_bar(f, chunks[0], app_state.current_tab, !app_state.subtab_focused);
+            render_tab_bar(f, chunks[0], &app_state.tabs, app_state.current_tab, !app_state.subtab_focused, focus_color);
 
             // Render sub-tabs and content based on current tab
             let content_chunk_idx = if app_state.current_tab == Tab::Scores {
-                render_scores_subtabs(f, chunks[1], &game_date, app_state.scores_selected_index, app_state.subtab_focused);
+                render_scores_subtabs(f, chunks[1], game_date, app_state.scores_selected_index, app_state.subtab_focused, focus_color);
                 2
             } else if app_state.current_tab == Tab::Standings {
-                render_standings_subtabs(f, chunks[1], app_state.standings_view, app_state.subtab_focused);
+                render_standings_subtabs(f, chunks[1], app_state.standings_view, app_state.subtab_focused && standings_header.is_none(), focus_color);
                 2
             } else {
                 1
             };
 
-            render_content(
-                f,
-                chunks[content_chunk_idx],
-                app_state.current_tab,
-                &standings_data,
-                &schedule_data,
-                &period_scores_data,
-                &game_info_data,
-                app_state.standings_view,
+            // Reuse the previous frame's content unless something it depends on changed
+            let content_area = chunks[content_chunk_idx];
+            let key = ContentKey {
+                tab: app_state.current_tab,
+                standings_view: app_state.standings_view,
+                standings_sort: app_state.standings_sort,
+                standings_columns: app_state.standings_columns.clone(),
+                standings_header,
                 western_first,
-            );
+                following_only: app_state.following_only,
+                width: content_area.width,
+                content_indent,
+                data_version,
+            };
+            let content = cached_content(&mut content_cache, key, || {
+                build_content(
+                    content_area.width as usize,
+                    app_state.current_tab,
+                    standings_groups_data,
+                    schedule_data,
+                    period_scores_data,
+                    game_info_data,
+                    app_state.standings_view,
+                    &app_state.standings_columns,
+                    standings_header,
+                    config,
+                )
+            });
+            render_content(f, content_area, content);
+
+            if has_ticker {
+                ticker::render_ticker(f, chunks[chunks.len() - 2], &ticker_items, app_state.ticker_offset);
+            }
 
             // Render status bar at the bottom
             let status_chunk_idx = chunks.len() - 1;
-            render_status_bar(f, chunks[status_chunk_idx], last_refresh, &time_format, error_message.as_deref());
+            render_status_bar(
+                f,
+                chunks[status_chunk_idx],
+                last_refresh,
+                refresh_interval,
+                time_format,
+                error_message.map(String::as_str),
+                prompt.as_ref().or(app_state.status_messages.current()),
+                unicode,
+            );
         })?;
 
+        // Save the frame that was just drawn if a screenshot was requested
+        if screenshot_requested {
+            screenshot_requested = false;
+            let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+            let tab = app_state.current_tab.name().to_lowercase();
+            let result = screenshot::next_screenshot_path(
+                std::path::Path::new(&config.screenshot_dir),
+                &config.screenshot_name_template,
+                &date,
+                &tab,
+                screenshot::format_extension(&config.screenshot_format),
+            )
+            .and_then(|path| {
+                match config.screenshot_format.as_str() {
+                    "ansi" => screenshot::save_buffer_ansi(completed_frame.buffer, completed_frame.area, &path)?,
+                    "html" => screenshot::save_buffer_html(completed_frame.buffer, completed_frame.area, &path)?,
+                    _ => screenshot::save_buffer_screenshot(completed_frame.buffer, completed_frame.area, &path)?,
+                }
+                Ok(path)
+            });
+            match result {
+                Ok(path) => app_state.status_messages.push_info(format!("Screenshot saved to {}", path.display())),
+                Err(e) => app_state.status_messages.push_error(format!("Failed to save screenshot: {}", e)),
+            }
+        }
+
+        // Copy the whole tab content, including what's scrolled off screen, without the margin
+        if copy_requested {
+            copy_requested = false;
+            let text = content_cache.as_ref().map_or(String::new(), |(_, content)| clipboard::content_text(content, content_indent));
+            match clipboard::copy(&text) {
+                Ok(()) => app_state.status_messages.push_info(format!("Copied {} lines to the clipboard", text.lines().count())),
+                Err(e) => app_state.status_messages.push_error(format!("Failed to copy: {}", e)),
+            }
+        }
+
+        drop(data);
+
         // Handle events
         if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                match handle_key_event(key, &mut app_state, &shared_data, &refresh_tx).await {
+            match event::read()? {
+                Event::Key(key) => match handle_key_event(key, &mut app_state, &shared_data).await {
                     AppAction::Exit => break,
+                    AppAction::Screenshot => screenshot_requested = true,
+                    AppAction::Copy => copy_requested = true,
+                    AppAction::Refresh => {
+                        let _ = refresh_tx.send(app_state.current_tab.refresh_scope()).await;
+                    }
                     AppAction::Continue => {}
-                }
+                },
+                Event::Mouse(mouse) => handle_mouse_event(mouse, &mut app_state),
+                _ => {}
             }
         }
     }
@@ -123,5 +337,110 @@ pub async fn run(shared_data: SharedDataHandle, refresh_tx: mpsc::Sender<()>) ->
     )?;
     terminal.show_cursor()?;
 
+    if restore_session {
+        let game_date = crate::game_date_key(&shared_data.read().await.game_date);
+        let session = snapshot(&app_state, game_date);
+        if let Err(e) = session::save(&session) {
+            eprintln!("Failed to save session: {}", e);
+        }
+    }
+
     Ok(())
 }
+
+/// Reopen a saved session, skipping whatever no longer applies (a hidden tab, an unreadable date)
+/// What to save of the app state so the next launch can reopen it
+fn snapshot(app_state: &AppState, game_date: String) -> session::Session {
+    session::Session {
+        tab: app_state.current_tab.name().to_string(),
+        standings_view: app_state.standings_view.name().to_string(),
+        game_date,
+        scores_selected_index: app_state.scores_selected_index,
+        standings_columns: app_state.standings_columns.iter().map(|column| column.name().to_string()).collect(),
+    }
+}
+
+async fn restore(session: &session::Session, app_state: &mut AppState, shared_data: &SharedDataHandle) {
+    if let Some(tab) = Tab::parse(&session.tab).filter(|tab| app_state.tabs.contains(tab)) {
+        app_state.current_tab = tab;
+    }
+    if let Some(view) = crate::commands::standings::GroupBy::all().into_iter().find(|view| view.name() == session.standings_view) {
+        app_state.standings_view = view;
+    }
+    if !session.standings_columns.is_empty() {
+        app_state.standings_columns = crate::commands::standings::parse_columns(&session.standings_columns);
+        let sort = &mut app_state.standings_sort;
+        if !app_state.standings_columns.contains(&sort.column) {
+            sort.column = sort.column.next_shown(&app_state.standings_columns);
+        }
+    }
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(&session.game_date, "%Y-%m-%d") {
+        app_state.scores_selected_index = session.scores_selected_index.min(2);
+        let mut data = shared_data.write().await;
+        data.game_date = nhl_api::GameDate::Date(date);
+        data.data_version += 1;
+        // The initial fetch may already be loading today's schedule
+        data.fetch_epoch += 1;
+        drop(data);
+        // Fetched like a date navigation once the loop starts
+        app_state.date_changed_at = Some(std::time::Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::standings::{toggle_column, SortColumn};
+
+    #[tokio::test]
+    async fn toggled_columns_survive_a_session_round_trip() {
+        let mut app_state = AppState::default();
+        toggle_column(&mut app_state.standings_columns, SortColumn::Losses);
+        toggle_column(&mut app_state.standings_columns, SortColumn::Points);
+        let saved = toml::to_string(&snapshot(&app_state, "2024-11-02".to_string())).unwrap();
+
+        let session: session::Session = toml::from_str(&saved).unwrap();
+        let mut restored = AppState::default();
+        let shared_data: SharedDataHandle = Default::default();
+        restore(&session, &mut restored, &shared_data).await;
+        assert_eq!(restored.standings_columns, vec![SortColumn::GamesPlayed, SortColumn::Wins, SortColumn::OtLosses]);
+        // The default sort column is hidden, so the sort moves to a shown one
+        assert_eq!(restored.standings_sort.column, SortColumn::GamesPlayed);
+    }
+
+    fn content_key() -> ContentKey {
+        ContentKey {
+            tab: Tab::Standings,
+            standings_view: crate::commands::standings::GroupBy::Division,
+            standings_sort: StandingsSort::default(),
+            standings_columns: SortColumn::all().to_vec(),
+            standings_header: None,
+            western_first: false,
+            following_only: false,
+            width: 120,
+            content_indent: 2,
+            data_version: 1,
+        }
+    }
+
+    #[test]
+    fn content_is_rebuilt_only_when_its_key_changes() {
+        let mut cache = None;
+        let mut builds = 0;
+        let mut build = |key: ContentKey, cache: &mut Option<(ContentKey, String)>| {
+            cached_content(cache, key, || {
+                builds += 1;
+                format!("build {}", builds)
+            })
+            .to_string()
+        };
+
+        assert_eq!(build(content_key(), &mut cache), "build 1");
+        assert_eq!(build(content_key(), &mut cache), "build 1");
+        assert_eq!(build(ContentKey { data_version: 2, ..content_key() }, &mut cache), "build 2");
+        assert_eq!(build(ContentKey { data_version: 2, ..content_key() }, &mut cache), "build 2");
+        assert_eq!(build(ContentKey { data_version: 2, width: 80, ..content_key() }, &mut cache), "build 3");
+        assert_eq!(build(ContentKey { standings_header: Some(SortColumn::Wins), ..content_key() }, &mut cache), "build 4");
+        assert_eq!(builds, 4);
+    }
+}
@@ -0,0 +1,385 @@
+use ratatui::{
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    style::{Color, Modifier},
+};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const COUNTER_PLACEHOLDER: &str = "{counter}";
+
+/// Convert the given area of a buffer to plain text, one line per row
+pub fn buffer_to_text(buffer: &Buffer, area: Rect) -> String {
+    let mut output = String::new();
+
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        for x in area.left()..area.right() {
+            line.push_str(buffer[(x, y)].symbol());
+        }
+        // Trailing spaces only come from padding, drop them
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Write the given area of a buffer to a text file
+pub fn save_buffer_screenshot(buffer: &Buffer, area: Rect, path: &Path) -> io::Result<()> {
+    fs::write(path, buffer_to_text(buffer, area))
+}
+
+/// ANSI SGR parameter for a foreground color (add 10 for the background variant)
+fn ansi_color_code(color: Color, background: bool) -> String {
+    let offset = if background { 10 } else { 0 };
+    match color {
+        Color::Reset => format!("{}", 39 + offset),
+        Color::Black => format!("{}", 30 + offset),
+        Color::Red => format!("{}", 31 + offset),
+        Color::Green => format!("{}", 32 + offset),
+        Color::Yellow => format!("{}", 33 + offset),
+        Color::Blue => format!("{}", 34 + offset),
+        Color::Magenta => format!("{}", 35 + offset),
+        Color::Cyan => format!("{}", 36 + offset),
+        Color::Gray => format!("{}", 37 + offset),
+        Color::DarkGray => format!("{}", 90 + offset),
+        Color::LightRed => format!("{}", 91 + offset),
+        Color::LightGreen => format!("{}", 92 + offset),
+        Color::LightYellow => format!("{}", 93 + offset),
+        Color::LightBlue => format!("{}", 94 + offset),
+        Color::LightMagenta => format!("{}", 95 + offset),
+        Color::LightCyan => format!("{}", 96 + offset),
+        Color::White => format!("{}", 97 + offset),
+        Color::Rgb(r, g, b) => format!("{};2;{};{};{}", 38 + offset, r, g, b),
+        Color::Indexed(i) => format!("{};5;{}", 38 + offset, i),
+    }
+}
+
+/// Full SGR escape sequence reproducing a cell's colors and modifiers
+fn ansi_style_sequence(cell: &Cell) -> String {
+    let mut codes = vec!["0".to_string()];
+
+    let modifiers = [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::SLOW_BLINK, "5"),
+        (Modifier::RAPID_BLINK, "6"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::HIDDEN, "8"),
+        (Modifier::CROSSED_OUT, "9"),
+    ];
+    for (modifier, code) in modifiers {
+        if cell.modifier.contains(modifier) {
+            codes.push(code.to_string());
+        }
+    }
+
+    if cell.fg != Color::Reset {
+        codes.push(ansi_color_code(cell.fg, false));
+    }
+    if cell.bg != Color::Reset {
+        codes.push(ansi_color_code(cell.bg, true));
+    }
+
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+/// Convert the given area of a buffer to text with ANSI escape codes reproducing its styles
+pub fn buffer_to_ansi(buffer: &Buffer, area: Rect) -> String {
+    let mut output = String::new();
+
+    for y in area.top()..area.bottom() {
+        let mut current_style = None;
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            // Only emit a new sequence when the style changes
+            let style = (cell.fg, cell.bg, cell.modifier);
+            if current_style != Some(style) {
+                output.push_str(&ansi_style_sequence(cell));
+                current_style = Some(style);
+            }
+            output.push_str(cell.symbol());
+        }
+        // Reset at the end of each line so styles don't bleed into the next one
+        output.push_str("\x1b[0m\n");
+    }
+
+    output
+}
+
+/// Write the given area of a buffer to a file, keeping colors as ANSI escape codes
+pub fn save_buffer_ansi(buffer: &Buffer, area: Rect, path: &Path) -> io::Result<()> {
+    fs::write(path, buffer_to_ansi(buffer, area))
+}
+
+/// Default colors of the exported HTML page, used for `Color::Reset`
+const HTML_DEFAULT_FG: &str = "#e5e5e5";
+const HTML_DEFAULT_BG: &str = "#000000";
+
+/// CSS color for a ratatui color, `None` meaning the page default
+fn css_color(color: Color) -> Option<String> {
+    let hex = |r: u8, g: u8, b: u8| format!("#{:02x}{:02x}{:02x}", r, g, b);
+    let color = match color {
+        Color::Reset => return None,
+        Color::Black => hex(0, 0, 0),
+        Color::Red => hex(205, 0, 0),
+        Color::Green => hex(0, 205, 0),
+        Color::Yellow => hex(205, 205, 0),
+        Color::Blue => hex(0, 0, 238),
+        Color::Magenta => hex(205, 0, 205),
+        Color::Cyan => hex(0, 205, 205),
+        Color::Gray => hex(229, 229, 229),
+        Color::DarkGray => hex(127, 127, 127),
+        Color::LightRed => hex(255, 0, 0),
+        Color::LightGreen => hex(0, 255, 0),
+        Color::LightYellow => hex(255, 255, 0),
+        Color::LightBlue => hex(92, 92, 255),
+        Color::LightMagenta => hex(255, 0, 255),
+        Color::LightCyan => hex(0, 255, 255),
+        Color::White => hex(255, 255, 255),
+        Color::Rgb(r, g, b) => hex(r, g, b),
+        Color::Indexed(i) => match i {
+            // The 16 base colors share the named palette above
+            0..=15 => {
+                let named = [
+                    Color::Black, Color::Red, Color::Green, Color::Yellow,
+                    Color::Blue, Color::Magenta, Color::Cyan, Color::Gray,
+                    Color::DarkGray, Color::LightRed, Color::LightGreen, Color::LightYellow,
+                    Color::LightBlue, Color::LightMagenta, Color::LightCyan, Color::White,
+                ];
+                return css_color(named[i as usize]);
+            }
+            // 6x6x6 color cube
+            16..=231 => {
+                let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+                let i = i - 16;
+                hex(level(i / 36), level((i / 6) % 6), level(i % 6))
+            }
+            // Grayscale ramp
+            _ => {
+                let v = 8 + (i - 232) * 10;
+                hex(v, v, v)
+            }
+        },
+    };
+    Some(color)
+}
+
+/// Inline CSS reproducing a cell's colors and modifiers
+fn css_style(cell: &Cell) -> String {
+    let mut fg = css_color(cell.fg).unwrap_or_else(|| HTML_DEFAULT_FG.to_string());
+    let mut bg = css_color(cell.bg).unwrap_or_else(|| HTML_DEFAULT_BG.to_string());
+    if cell.modifier.contains(Modifier::REVERSED) {
+        std::mem::swap(&mut fg, &mut bg);
+    }
+
+    let mut style = format!("color:{};background-color:{}", fg, bg);
+    if cell.modifier.contains(Modifier::BOLD) {
+        style.push_str(";font-weight:bold");
+    }
+    if cell.modifier.contains(Modifier::DIM) {
+        style.push_str(";opacity:0.6");
+    }
+    if cell.modifier.contains(Modifier::ITALIC) {
+        style.push_str(";font-style:italic");
+    }
+    match (
+        cell.modifier.contains(Modifier::UNDERLINED),
+        cell.modifier.contains(Modifier::CROSSED_OUT),
+    ) {
+        (true, true) => style.push_str(";text-decoration:underline line-through"),
+        (true, false) => style.push_str(";text-decoration:underline"),
+        (false, true) => style.push_str(";text-decoration:line-through"),
+        (false, false) => {}
+    }
+    if cell.modifier.contains(Modifier::HIDDEN) {
+        style.push_str(";visibility:hidden");
+    }
+
+    style
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Convert the given area of a buffer to a standalone HTML page with inline styles
+pub fn buffer_to_html(buffer: &Buffer, area: Rect) -> String {
+    let mut output = String::new();
+    output.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>nhl</title>\n</head>\n");
+    output.push_str(&format!(
+        "<body style=\"background-color:{};color:{}\">\n<pre style=\"font-family:monospace;line-height:1.2\">\n",
+        HTML_DEFAULT_BG, HTML_DEFAULT_FG
+    ));
+
+    for y in area.top()..area.bottom() {
+        // Group consecutive cells sharing a style into a single span
+        let mut current_style: Option<String> = None;
+        let mut text = String::new();
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            let style = css_style(cell);
+            if current_style.as_ref() != Some(&style) {
+                if let Some(previous) = current_style.take() {
+                    output.push_str(&format!("<span style=\"{}\">{}</span>", previous, escape_html(&text)));
+                    text.clear();
+                }
+                current_style = Some(style);
+            }
+            text.push_str(cell.symbol());
+        }
+        if let Some(style) = current_style {
+            output.push_str(&format!("<span style=\"{}\">{}</span>", style, escape_html(&text)));
+        }
+        output.push('\n');
+    }
+
+    output.push_str("</pre>\n</body>\n</html>\n");
+    output
+}
+
+/// Write the given area of a buffer to a standalone HTML file
+pub fn save_buffer_html(buffer: &Buffer, area: Rect, path: &Path) -> io::Result<()> {
+    fs::write(path, buffer_to_html(buffer, area))
+}
+
+/// File extension for a screenshot format ("text", "ansi" or "html")
+pub fn format_extension(format: &str) -> &'static str {
+    match format {
+        "ansi" => "ans",
+        "html" => "html",
+        _ => "txt",
+    }
+}
+
+/// Expand the `{date}`, `{tab}` and `{ext}` placeholders of a filename template,
+/// leaving `{counter}` in place
+fn expand_template(template: &str, date: &str, tab: &str, ext: &str) -> String {
+    template.replace("{date}", date).replace("{tab}", tab).replace("{ext}", ext)
+}
+
+/// Find the next free screenshot counter by scanning existing files in `dir`
+/// that match the (partially expanded) filename template
+pub fn get_next_screenshot_counter(dir: &Path, template: &str) -> u32 {
+    // Without a counter placeholder there is nothing to increment
+    let (prefix, suffix) = match template.split_once(COUNTER_PLACEHOLDER) {
+        Some(parts) => parts,
+        None => return 1,
+    };
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return 1,
+    };
+
+    let max_counter = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.strip_prefix(prefix)?
+                .strip_suffix(suffix)?
+                .parse::<u32>()
+                .ok()
+        })
+        .max()
+        .unwrap_or(0);
+
+    max_counter + 1
+}
+
+/// Build the path of the next screenshot file, creating `dir` if it doesn't exist
+pub fn next_screenshot_path(dir: &Path, template: &str, date: &str, tab: &str, ext: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+
+    let template = expand_template(template, date, tab, ext);
+    let counter = get_next_screenshot_counter(dir, &template);
+    let file_name = template.replace(COUNTER_PLACEHOLDER, &format!("{:03}", counter));
+
+    Ok(dir.join(file_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_expands_everything_but_the_counter() {
+        assert_eq!(
+            expand_template("nhl-{date}-{tab}-{counter}.{ext}", "2024-11-02", "scores", "html"),
+            "nhl-2024-11-02-scores-{counter}.html"
+        );
+        assert_eq!(expand_template("shot.txt", "2024-11-02", "scores", "txt"), "shot.txt");
+    }
+
+    #[test]
+    fn counter_follows_the_highest_existing_file() {
+        let dir = std::env::temp_dir().join(format!("nhl-screenshot-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(get_next_screenshot_counter(&dir, "shot-{counter}.txt"), 1);
+
+        for name in ["shot-001.txt", "shot-007.txt", "shot-009.html", "other-012.txt", "shot-abc.txt"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        assert_eq!(get_next_screenshot_counter(&dir, "shot-{counter}.txt"), 8);
+        assert_eq!(get_next_screenshot_counter(&dir, "shot-{counter}.html"), 10);
+        assert_eq!(next_screenshot_path(&dir, "shot-{counter}.{ext}", "", "", "txt").unwrap(), dir.join("shot-008.txt"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn buffer_text_keeps_the_area_without_trailing_padding() {
+        let area = Rect::new(0, 0, 8, 2);
+        let mut buffer = Buffer::empty(area);
+        buffer.set_string(0, 0, "BOS 3", ratatui::style::Style::default());
+        buffer.set_string(2, 1, "TOR", ratatui::style::Style::default());
+        assert_eq!(buffer_to_text(&buffer, area), "BOS 3\n  TOR\n");
+        assert_eq!(buffer_to_text(&buffer, Rect::new(2, 1, 3, 1)), "TOR\n");
+    }
+
+    #[test]
+    fn ansi_export_emits_sgr_sequences_on_style_changes() {
+        use ratatui::style::Style;
+
+        let area = Rect::new(0, 0, 6, 1);
+        let mut buffer = Buffer::empty(area);
+        buffer.set_string(0, 0, "BOS", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD));
+        buffer.set_string(3, 0, " ", Style::default());
+        buffer.set_string(4, 0, "3", Style::default().fg(Color::Rgb(255, 128, 0)).bg(Color::Indexed(17)));
+
+        assert_eq!(
+            buffer_to_ansi(&buffer, area),
+            "\x1b[0;1;32mBOS\x1b[0m \x1b[0;38;2;255;128;0;48;5;17m3\x1b[0m \x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn html_export_groups_styled_spans_and_escapes_text() {
+        use ratatui::style::Style;
+
+        let area = Rect::new(0, 0, 7, 1);
+        let mut buffer = Buffer::empty(area);
+        buffer.set_string(0, 0, "<B&", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+        buffer.set_string(3, 0, " ", Style::default());
+        buffer.set_string(4, 0, "A>", Style::default().fg(Color::Black).bg(Color::Rgb(0, 128, 255)).add_modifier(Modifier::REVERSED));
+
+        let html = buffer_to_html(&buffer, area);
+        let row = html.lines().find(|line| line.starts_with("<span")).unwrap();
+        assert_eq!(
+            row,
+            concat!(
+                "<span style=\"color:#cd0000;background-color:#000000;font-weight:bold\">&lt;B&amp;</span>",
+                "<span style=\"color:#e5e5e5;background-color:#000000\"> </span>",
+                // Reversed cells swap their colors
+                "<span style=\"color:#0080ff;background-color:#000000\">A&gt;</span>",
+                "<span style=\"color:#e5e5e5;background-color:#000000\"> </span>",
+            )
+        );
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.ends_with("</pre>\n</body>\n</html>\n"));
+    }
+}
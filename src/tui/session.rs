@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use xdg::BaseDirectories;
+
+/// What the TUI was showing on exit, saved so the next launch can reopen it.
+/// Names are stored rather than enum values so a renamed or removed tab just
+/// fails to restore instead of breaking the file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub tab: String,
+    pub standings_view: String,
+    pub game_date: String, // YYYY-MM-DD
+    pub scores_selected_index: usize,
+    #[serde(default)]
+    pub standings_columns: Vec<String>, // Shown standings columns; empty keeps the configured ones
+}
+
+fn session_path() -> Option<PathBuf> {
+    let xdg_dirs = BaseDirectories::with_prefix(env!("CARGO_PKG_NAME"));
+    Some(xdg_dirs.get_state_home()?.join("session.toml"))
+}
+
+/// The last saved session, or None if there is none or it can't be read
+pub fn load() -> Option<Session> {
+    load_from(&session_path()?)
+}
+
+fn load_from(path: &Path) -> Option<Session> {
+    let contents = fs::read_to_string(path).ok()?;
+    toml::from_str(&contents).ok()
+}
+
+pub fn save(session: &Session) -> std::io::Result<()> {
+    let path = session_path().ok_or_else(|| std::io::Error::other("Unable to determine state directory"))?;
+    save_to(&path, session)
+}
+
+fn save_to(path: &Path, session: &Session) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let contents = toml::to_string(session).map_err(std::io::Error::other)?;
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_round_trip_and_bad_files_are_ignored() {
+        let dir = std::env::temp_dir().join(format!("nhl-session-test-{}", std::process::id()));
+        let path = dir.join("state").join("session.toml");
+        let _ = fs::remove_dir_all(&dir);
+
+        // No file yet: nothing to restore, so the defaults stay
+        assert_eq!(load_from(&path), None);
+
+        let session = Session {
+            tab: "Standings".to_string(),
+            standings_view: "Conference".to_string(),
+            game_date: "2024-11-02".to_string(),
+            scores_selected_index: 2,
+            standings_columns: vec!["W".to_string(), "PTS".to_string()],
+        };
+        save_to(&path, &session).unwrap();
+        assert_eq!(load_from(&path), Some(session));
+
+        fs::write(&path, "tab = [not a session").unwrap();
+        assert_eq!(load_from(&path), None);
+
+        // Files from before standings_columns was saved still load
+        fs::write(&path, "tab = \"Scores\"\nstandings_view = \"Division\"\ngame_date = \"2024-11-02\"\nscores_selected_index = 1\n").unwrap();
+        assert_eq!(load_from(&path).map(|session| session.standings_columns), Some(Vec::new()));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
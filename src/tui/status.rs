@@ -0,0 +1,116 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long messages stay in the status bar; errors linger longer than info
+const INFO_TTL: Duration = Duration::from_secs(4);
+const ERROR_TTL: Duration = Duration::from_secs(10);
+
+/// Most messages kept at once; older ones are dropped first
+const MAX_MESSAGES: usize = 5;
+
+#[derive(Debug, Clone)]
+pub struct StatusMessage {
+    pub text: String,
+    pub is_error: bool,
+    pub created: Instant,
+}
+
+impl StatusMessage {
+    fn ttl(&self) -> Duration {
+        if self.is_error {
+            ERROR_TTL
+        } else {
+            INFO_TTL
+        }
+    }
+
+    /// Whether the message is still within its time-to-live at `now`
+    pub fn should_show(&self, now: Instant) -> bool {
+        now.duration_since(self.created) < self.ttl()
+    }
+}
+
+/// Bounded queue of status messages, the most recent one being displayed
+#[derive(Debug, Default)]
+pub struct StatusQueue {
+    messages: VecDeque<StatusMessage>,
+}
+
+impl StatusQueue {
+    pub fn push_info(&mut self, text: String) {
+        self.push(text, false);
+    }
+
+    pub fn push_error(&mut self, text: String) {
+        self.push(text, true);
+    }
+
+    fn push(&mut self, text: String, is_error: bool) {
+        if self.messages.len() == MAX_MESSAGES {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(StatusMessage {
+            text,
+            is_error,
+            created: Instant::now(),
+        });
+    }
+
+    /// Drop the messages whose time-to-live has run out
+    pub fn expire(&mut self, now: Instant) {
+        self.messages.retain(|message| message.should_show(now));
+    }
+
+    /// The message to display: the most recent one still queued
+    pub fn current(&self) -> Option<&StatusMessage> {
+        self.messages.back()
+    }
+
+    /// Drop the info messages; errors stay until their time-to-live runs out
+    pub fn clear_info(&mut self) {
+        self.messages.retain(|message| message.is_error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_error_outlives_an_info_message() {
+        let mut queue = StatusQueue::default();
+        queue.push_error("Failed to save screenshot".to_string());
+        queue.push_info("Copied 12 lines".to_string());
+        let created = queue.messages[0].created;
+
+        queue.expire(created + INFO_TTL - Duration::from_millis(1));
+        assert_eq!(queue.current().map(|message| message.text.as_str()), Some("Copied 12 lines"));
+
+        // The info message expires first, uncovering the older error
+        queue.expire(created + INFO_TTL + Duration::from_millis(1));
+        assert_eq!(queue.current().map(|message| message.text.as_str()), Some("Failed to save screenshot"));
+
+        queue.expire(created + ERROR_TTL + Duration::from_secs(1));
+        assert!(queue.current().is_none());
+    }
+
+    #[test]
+    fn the_oldest_message_is_dropped_when_full() {
+        let mut queue = StatusQueue::default();
+        for i in 0..=MAX_MESSAGES {
+            queue.push_info(format!("message {}", i));
+        }
+        assert_eq!(queue.messages.len(), MAX_MESSAGES);
+        assert_eq!(queue.messages[0].text, "message 1");
+        assert_eq!(queue.current().map(|message| message.text.as_str()), Some("message 5"));
+    }
+
+    #[test]
+    fn a_key_press_clears_only_info_messages() {
+        let mut queue = StatusQueue::default();
+        queue.push_error("Failed to copy".to_string());
+        queue.push_info("Sorted by W".to_string());
+        queue.clear_info();
+        assert_eq!(queue.current().map(|message| message.text.as_str()), Some("Failed to copy"));
+    }
+}
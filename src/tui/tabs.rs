@@ -1,4 +1,7 @@
-use crate::commands::standings::GroupBy;
+use crate::commands::standings::{GroupBy, SortColumn, StandingsSort};
+use crate::RefreshScope;
+use super::status::StatusQueue;
+use std::time::Instant;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Tab {
@@ -16,25 +19,149 @@ impl Tab {
         }
     }
 
+    /// The data the tab displays, which is all a refresh from it needs to fetch
+    pub fn refresh_scope(&self) -> RefreshScope {
+        match self {
+            Tab::Scores => RefreshScope::Schedule,
+            Tab::Standings => RefreshScope::Standings,
+            Tab::Settings => RefreshScope::All,
+        }
+    }
+
     pub fn all() -> [Tab; 3] {
         [Tab::Scores, Tab::Standings, Tab::Settings]
     }
+
+    /// The tab named `name`, ignoring case
+    pub fn parse(name: &str) -> Option<Tab> {
+        Tab::all().into_iter().find(|tab| tab.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// The tab to the right in `tabs`, wrapping around to the first one or stopping at the last
+    pub fn next(&self, tabs: &[Tab], wrap: bool) -> Tab {
+        let index = tabs.iter().position(|tab| tab == self).unwrap_or(0);
+        if wrap {
+            tabs[(index + 1) % tabs.len()]
+        } else {
+            tabs[(index + 1).min(tabs.len() - 1)]
+        }
+    }
+
+    /// The tab to the left in `tabs`, wrapping around to the last one or stopping at the first
+    pub fn prev(&self, tabs: &[Tab], wrap: bool) -> Tab {
+        let index = tabs.iter().position(|tab| tab == self).unwrap_or(0);
+        if wrap {
+            tabs[(index + tabs.len() - 1) % tabs.len()]
+        } else {
+            tabs[index.saturating_sub(1)]
+        }
+    }
+
+    /// The first of `tabs` whose name starts with `prefix`, ignoring case
+    pub fn matching(prefix: &str, tabs: &[Tab]) -> Option<Tab> {
+        if prefix.is_empty() {
+            return None;
+        }
+        let prefix = prefix.to_lowercase();
+        tabs.iter().copied().find(|tab| tab.name().to_lowercase().starts_with(&prefix))
+    }
 }
 
 pub struct AppState {
+    pub tabs: Vec<Tab>, // Tabs shown, in order; never empty
+    pub tab_nav_wrap: bool, // Whether tab navigation wraps around at the ends
     pub current_tab: Tab,
     pub standings_view: GroupBy,
+    pub standings_sort: StandingsSort,
+    pub standings_columns: Vec<SortColumn>, // Standings columns shown, in table order; never empty
+    pub standings_header: Option<SortColumn>, // Column selected in the standings header row, while it has focus
     pub subtab_focused: bool,
     pub scores_selected_index: usize, // 0 = left, 1 = middle, 2 = right
+    pub status_messages: StatusQueue,
+    pub ticker_offset: usize,
+    pub following_only: bool,
+    pub date_changed_at: Option<Instant>, // Last scores date change not yet fetched
+    pub tab_jump: Option<String>, // Tab name typed after ':', while the prompt is open
+    pub count: Option<u32>, // Repeat count typed before a date navigation key
 }
 
 impl Default for AppState {
     fn default() -> Self {
         AppState {
+            tabs: Tab::all().to_vec(),
+            tab_nav_wrap: true,
             current_tab: Tab::Scores,
             standings_view: GroupBy::Division,
+            standings_sort: StandingsSort::default(),
+            standings_columns: SortColumn::all().to_vec(),
+            standings_header: None,
             subtab_focused: false,
             scores_selected_index: 1, // Start with middle date selected
+            status_messages: StatusQueue::default(),
+            ticker_offset: 0,
+            following_only: false,
+            date_changed_at: None,
+            tab_jump: None,
+            count: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tabs_parse_by_name() {
+        for tab in Tab::all() {
+            assert_eq!(Tab::parse(&tab.name().to_uppercase()), Some(tab));
+        }
+        assert_eq!(Tab::parse("browser"), None);
+    }
+
+    #[test]
+    fn navigation_follows_the_configured_tabs() {
+        let tabs = [Tab::Settings, Tab::Scores];
+        assert_eq!(Tab::Settings.next(&tabs, true), Tab::Scores);
+        assert_eq!(Tab::Scores.prev(&tabs, true), Tab::Settings);
+        // A tab missing from the list navigates from the first one
+        assert_eq!(Tab::Standings.next(&tabs, true), Tab::Scores);
+    }
+
+    #[test]
+    fn navigation_wraps_or_clamps_at_the_ends() {
+        let tabs = Tab::all();
+        assert_eq!(Tab::Settings.next(&tabs, true), Tab::Scores);
+        assert_eq!(Tab::Scores.prev(&tabs, true), Tab::Settings);
+        assert_eq!(Tab::Settings.next(&tabs, false), Tab::Settings);
+        assert_eq!(Tab::Scores.prev(&tabs, false), Tab::Scores);
+        // Away from the ends both move one tab
+        assert_eq!(Tab::Standings.next(&tabs, false), Tab::Settings);
+        assert_eq!(Tab::Standings.prev(&tabs, false), Tab::Scores);
+    }
+
+    #[test]
+    fn tab_prompt_matches_a_name_prefix() {
+        let tabs = Tab::all();
+        assert_eq!(Tab::matching("stand", &tabs), Some(Tab::Standings));
+        assert_eq!(Tab::matching("SET", &tabs), Some(Tab::Settings));
+        // An ambiguous prefix picks the first matching tab in bar order
+        assert_eq!(Tab::matching("s", &tabs), Some(Tab::Scores));
+        assert_eq!(Tab::matching("s", &[Tab::Settings, Tab::Scores]), Some(Tab::Settings));
+        // No match, or a tab that isn't shown
+        assert_eq!(Tab::matching("browser", &tabs), None);
+        assert_eq!(Tab::matching("", &tabs), None);
+        assert_eq!(Tab::matching("stand", &[Tab::Scores]), None);
+    }
+
+    #[test]
+    fn standings_tab_refreshes_only_the_standings() {
+        let scope = Tab::Standings.refresh_scope();
+        assert!(scope.includes_standings());
+        assert!(!scope.includes_schedule());
+
+        let scope = Tab::Scores.refresh_scope();
+        assert!(!scope.includes_standings());
+        assert!(scope.includes_schedule());
+    }
+}
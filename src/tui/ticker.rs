@@ -0,0 +1,102 @@
+use nhl_api::{DailySchedule, GameMatchup};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::Paragraph,
+    Frame,
+};
+use std::collections::HashMap;
+
+const ITEM_SEPARATOR: &str = "  •  ";
+
+fn period_label(period_type: &str, number: i32) -> String {
+    match period_type {
+        "OT" => "OT".to_string(),
+        "SO" => "SO".to_string(),
+        _ => match number {
+            1 => "1st".to_string(),
+            2 => "2nd".to_string(),
+            3 => "3rd".to_string(),
+            n => format!("{}th", n),
+        },
+    }
+}
+
+/// Format a single ticker entry, e.g. "BOS goal: Pastrnak (12:04 2nd)"
+pub fn format_ticker_item(team_abbrev: &str, scorer: &str, time_in_period: &str, period: &str) -> String {
+    format!("{} goal: {} ({} {})", team_abbrev, scorer, time_in_period, period)
+}
+
+/// Collect the scoring events of all live games, in schedule order
+pub fn ticker_items(schedule: &DailySchedule, game_info: &HashMap<i64, GameMatchup>) -> Vec<String> {
+    let mut items = Vec::new();
+
+    for game in &schedule.games {
+        if !game.game_state.has_started() || game.game_state.is_final() {
+            continue;
+        }
+        let summary = match game_info.get(&game.id).and_then(|info| info.summary.as_ref()) {
+            Some(summary) => summary,
+            None => continue,
+        };
+
+        for period in &summary.scoring {
+            let label = period_label(&period.period_descriptor.period_type, period.period_descriptor.number);
+            for goal in &period.goals {
+                items.push(format_ticker_item(
+                    &goal.team_abbrev.default,
+                    &goal.name.default,
+                    &goal.time_in_period,
+                    &label,
+                ));
+            }
+        }
+    }
+
+    items
+}
+
+/// Move the ticker to its next item, wrapping back to the first one at the end
+pub fn advance_offset(offset: usize, item_count: usize) -> usize {
+    if item_count == 0 {
+        0
+    } else {
+        (offset + 1) % item_count
+    }
+}
+
+/// Render the ticker on a single line, starting at `offset` and cycling through the items
+pub fn render_ticker(f: &mut Frame, area: Rect, items: &[String], offset: usize) {
+    let text = if items.is_empty() {
+        "No goals in live games".to_string()
+    } else {
+        let start = offset % items.len();
+        items[start..]
+            .iter()
+            .chain(items[..start].iter())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(ITEM_SEPARATOR)
+    };
+
+    let ticker = Paragraph::new(text).style(Style::default().fg(Color::Yellow));
+    f.render_widget(ticker, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_a_goal() {
+        assert_eq!(format_ticker_item("BOS", "Pastrnak", "12:04", "2nd"), "BOS goal: Pastrnak (12:04 2nd)");
+        assert_eq!(format_ticker_item("TOR", "Matthews", "03:15", &period_label("OT", 4)), "TOR goal: Matthews (03:15 OT)");
+    }
+
+    #[test]
+    fn offset_wraps_at_the_end() {
+        assert_eq!(advance_offset(0, 3), 1);
+        assert_eq!(advance_offset(2, 3), 0);
+        assert_eq!(advance_offset(5, 0), 0);
+    }
+}
@@ -5,10 +5,13 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
+use unicode_width::UnicodeWidthStr;
 use chrono::{DateTime, Local};
-use crate::commands::standings::GroupBy;
+use crate::commands::standings::{GroupBy, StandingsGroups};
 use super::tabs::Tab;
+use super::status::StatusMessage;
+use crate::commands::formatting::truncate_display;
 
 /// Helper function to build a separator line with box-drawing connectors for tabs
 fn build_tab_separator_line<'a, I>(tab_names: I, area_width: usize, style: Style) -> Line<'a>
@@ -21,14 +24,14 @@ where
     for (i, tab_name) in tab_names.enumerate() {
         if i > 0 {
             // Add horizontal line before separator
-            separator_spans.push(Span::raw("─".repeat(1)));
+            separator_spans.push(Span::raw("─".to_string()));
             separator_spans.push(Span::raw("┴"));
-            separator_spans.push(Span::raw("─".repeat(1)));
+            separator_spans.push(Span::raw("─".to_string()));
             pos += 3;
         }
         // Add horizontal line under tab
-        separator_spans.push(Span::raw("─".repeat(tab_name.len())));
-        pos += tab_name.len();
+        separator_spans.push(Span::raw("─".repeat(tab_name.width())));
+        pos += tab_name.width();
     }
 
     // Fill rest of line
@@ -39,16 +42,37 @@ where
     Line::from(separator_spans).style(style)
 }
 
-pub fn render_tab_bar(f: &mut Frame, area: Rect, current_tab: Tab, focused: bool) {
-    let tabs_vec = Tab::all();
+/// Width of the separator drawn between tab names
+const TAB_SEPARATOR_WIDTH: usize = 3; // " │ "
+
+/// The tab whose name is drawn at column `x` of the tab bar, if any
+pub fn tab_at(tabs: &[Tab], x: u16) -> Option<Tab> {
+    let x = x as usize;
+    let mut start = 0;
+    for tab in tabs {
+        let end = start + tab.name().width();
+        if (start..end).contains(&x) {
+            return Some(*tab);
+        }
+        start = end + TAB_SEPARATOR_WIDTH;
+    }
+    None
+}
+
+/// Base style of a tab bar: the focus color when it has focus, dimmed otherwise
+fn bar_style(focused: bool, focus_color: Option<Color>) -> Style {
+    match (focused, focus_color) {
+        (true, Some(color)) => Style::default().fg(color),
+        (true, None) => Style::default(),
+        (false, _) => Style::default().fg(Color::DarkGray),
+    }
+}
+
+pub fn render_tab_bar(f: &mut Frame, area: Rect, tabs_vec: &[Tab], current_tab: Tab, focused: bool, focus_color: Option<Color>) {
     let selected_index = tabs_vec.iter().position(|&t| t == current_tab).unwrap_or(0);
 
     // Determine base style based on focus
-    let base_style = if focused {
-        Style::default()
-    } else {
-        Style::default().fg(Color::DarkGray)
-    };
+    let base_style = bar_style(focused, focus_color);
 
     // Build tab line with separators
     let mut tab_spans = Vec::new();
@@ -57,7 +81,7 @@ pub fn render_tab_bar(f: &mut Frame, area: Rect, current_tab: Tab, focused: bool
             tab_spans.push(Span::styled(" │ ", base_style));
         }
 
-        let tab_text = format!("{}", tab.name());
+        let tab_text = tab.name().to_string();
         let style = if i == selected_index {
             base_style.add_modifier(Modifier::REVERSED)
         } else {
@@ -78,15 +102,11 @@ pub fn render_tab_bar(f: &mut Frame, area: Rect, current_tab: Tab, focused: bool
     f.render_widget(tabs_widget, area);
 }
 
-pub fn render_standings_subtabs(f: &mut Frame, area: Rect, standings_view: GroupBy, focused: bool) {
+pub fn render_standings_subtabs(f: &mut Frame, area: Rect, standings_view: GroupBy, focused: bool, focus_color: Option<Color>) {
     let views = GroupBy::all();
 
     // Determine base style based on focus
-    let base_style = if focused {
-        Style::default()
-    } else {
-        Style::default().fg(Color::DarkGray)
-    };
+    let base_style = bar_style(focused, focus_color);
 
     // Build subtab line with separators and left margin
     let mut subtab_spans = Vec::new();
@@ -97,7 +117,7 @@ pub fn render_standings_subtabs(f: &mut Frame, area: Rect, standings_view: Group
             subtab_spans.push(Span::styled(" │ ", base_style));
         }
 
-        let tab_text = format!("{}", view.name());
+        let tab_text = view.name().to_string();
         let style = if *view == standings_view {
             base_style.add_modifier(Modifier::REVERSED)
         } else {
@@ -124,13 +144,9 @@ pub fn render_standings_subtabs(f: &mut Frame, area: Rect, standings_view: Group
     f.render_widget(subtab_widget, area);
 }
 
-pub fn render_scores_subtabs(f: &mut Frame, area: Rect, game_date: &nhl_api::GameDate, selected_index: usize, focused: bool) {
+pub fn render_scores_subtabs(f: &mut Frame, area: Rect, game_date: &nhl_api::GameDate, selected_index: usize, focused: bool, focus_color: Option<Color>) {
     // Determine base style based on focus
-    let base_style = if focused {
-        Style::default()
-    } else {
-        Style::default().fg(Color::DarkGray)
-    };
+    let base_style = bar_style(focused, focus_color);
 
     // Calculate the three dates to display based on game_date and selected_index
     // game_date is always the selected date
@@ -207,10 +223,41 @@ pub fn render_scores_subtabs(f: &mut Frame, area: Rect, game_date: &nhl_api::Gam
     f.render_widget(subtab_widget, area);
 }
 
-pub fn render_status_bar(f: &mut Frame, area: Rect, last_refresh: Option<SystemTime>, time_format: &str, error_message: Option<&str>) {
-    if let Some(error) = error_message {
+/// Slack on top of the refresh interval for the fetch itself to complete
+const STALE_GRACE: Duration = Duration::from_secs(5);
+
+/// Whether data refreshed at `last_refresh` has missed its scheduled refresh by `now`
+pub fn is_stale(last_refresh: SystemTime, now: SystemTime, refresh_interval: Duration) -> bool {
+    now.duration_since(last_refresh)
+        .is_ok_and(|age| age > refresh_interval + STALE_GRACE)
+}
+
+/// Marker in front of a status message; plain ASCII when the terminal can't show the symbols
+fn message_prefix(is_error: bool, unicode: bool) -> &'static str {
+    match (is_error, unicode) {
+        (true, true) => "✖ ",
+        (false, true) => "ℹ ",
+        (true, false) => "! ",
+        (false, false) => "i ",
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_status_bar(
+    f: &mut Frame,
+    area: Rect,
+    last_refresh: Option<SystemTime>,
+    refresh_interval: Duration,
+    time_format: &str,
+    error_message: Option<&str>,
+    status_message: Option<&StatusMessage>,
+    unicode: bool,
+) {
+    // A prompt or status message takes priority over the persistent fetch error,
+    // so whatever is typed into a prompt stays visible
+    if let (Some(error), None) = (error_message, status_message) {
         // Display error message in red if present
-        let error_line = format!("ERROR: {}", error);
+        let error_line = truncate_display(&format!("ERROR: {}", error), area.width as usize, "…");
         let status_line = format!("{:width$}", error_line, width = area.width as usize);
         let status_bar = Paragraph::new(status_line)
             .style(Style::default().bg(Color::Red).fg(Color::White));
@@ -218,34 +265,56 @@ pub fn render_status_bar(f: &mut Frame, area: Rect, last_refresh: Option<SystemT
         return;
     }
 
-    // Normal status display
-    let status_text = if let Some(refresh_time) = last_refresh {
+    // Normal status display, with the status message (if any) on the left
+    let refresh_text = if let Some(refresh_time) = last_refresh {
         let datetime: DateTime<Local> = refresh_time.into();
         let formatted_time = datetime.format(time_format).to_string();
-        format!("last refresh: {}", formatted_time)
+        if is_stale(refresh_time, SystemTime::now(), refresh_interval) {
+            format!("data from {} (stale)", formatted_time)
+        } else {
+            format!("last refresh: {}", formatted_time)
+        }
     } else {
         "last refresh: never".to_string()
     };
 
-    // Create a line that fills the entire width with spaces (for reverse video background)
-    let status_line = format!("{:>width$}", status_text, width = area.width as usize);
-    let status_bar = Paragraph::new(status_line)
+    // Errors and info messages get their own prefix and color on the left
+    let message_span = status_message.map(|message| {
+        let prefix = message_prefix(message.is_error, unicode);
+        let color = if message.is_error { Color::Red } else { Color::Blue };
+        // Keep the refresh time visible, cutting the message if both don't fit
+        let available = (area.width as usize).saturating_sub(refresh_text.width() + 1);
+        let text = truncate_display(&format!("{}{}", prefix, message.text), available, "…");
+        Span::styled(text, Style::default().fg(color).add_modifier(Modifier::BOLD))
+    });
+
+    // Fill the entire width with spaces (for reverse video background)
+    let message_width = message_span.as_ref().map(|span| span.content.width()).unwrap_or(0);
+    let width = (area.width as usize).saturating_sub(message_width);
+    let mut spans: Vec<Span> = message_span.into_iter().collect();
+    spans.push(Span::raw(format!("{:>width$}", refresh_text, width = width)));
+
+    let status_bar = Paragraph::new(Line::from(spans))
         .style(Style::default().bg(Color::White).fg(Color::Black));
 
     f.render_widget(status_bar, area);
 }
 
-pub fn render_content(
-    f: &mut Frame,
-    area: Rect,
+/// Build the text of the current tab's content for an area `width` columns wide
+#[allow(clippy::too_many_arguments)]
+pub fn build_content(
+    width: usize,
     current_tab: Tab,
-    standings_data: &[nhl_api::Standing],
+    standings_groups: &StandingsGroups,
     schedule_data: &Option<nhl_api::DailySchedule>,
     period_scores: &std::collections::HashMap<i64, crate::commands::scores_format::PeriodScores>,
     game_info: &std::collections::HashMap<i64, nhl_api::GameMatchup>,
     standings_view: GroupBy,
-    western_first: bool,
-) {
+    standings_columns: &[crate::commands::standings::SortColumn],
+    standings_header: Option<crate::commands::standings::SortColumn>,
+    config: &crate::config::Config,
+) -> String {
+    let content_indent = config.content_indent;
     let content = match current_tab {
         Tab::Scores => {
             if let Some(schedule) = schedule_data {
@@ -254,29 +323,211 @@ pub fn render_content(
                     schedule,
                     period_scores,
                     game_info,
-                    Some(area.width as usize)
+                    Some(width.saturating_sub(content_indent))
                 )
             } else {
                 "Loading scores...".to_string()
             }
         }
         Tab::Standings => {
-            let standings_text = crate::commands::standings::format_standings_by_group(
-                standings_data,
+            // Stack the conference/division columns when they don't fit side by side
+            let two_columns = width.saturating_sub(content_indent) >= config.standings_stack_width;
+            let format = crate::commands::standings::TableFormat {
+                separator_style: crate::commands::formatting::SeparatorStyle::parse(&config.separator_style)
+                    .unwrap_or_default()
+                    .for_terminal(crate::commands::locale_is_utf8()),
+                columns: standings_columns.to_vec(),
+                selected: standings_header,
+            };
+            crate::commands::standings::format_standings_groups(
+                standings_groups,
                 standings_view,
-                western_first,
-            );
-            // Add 2-space left padding to each line to align with sub-tab line
-            standings_text
-                .lines()
-                .map(|line| format!("  {}", line))
-                .collect::<Vec<_>>()
-                .join("\n")
+                config.display_standings_western_first,
+                two_columns,
+                false,
+                &format,
+            )
         }
         _ => "...".to_string(),
     };
 
+    // Indent every line by the configured margin (the default lines up with the sub-tab line)
+    let indent = " ".repeat(content_indent);
+    content
+        .lines()
+        .map(|line| format!("{}{}", indent, line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn render_content(f: &mut Frame, area: Rect, content: &str) {
     let paragraph = Paragraph::new(content).block(Block::default().borders(Borders::NONE));
 
     f.render_widget(paragraph, area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::buffer::Buffer;
+    use crate::commands::test_support::standing;
+    use std::collections::HashMap;
+
+    /// One team in each division
+    fn standings_groups() -> StandingsGroups {
+        StandingsGroups::new(&[
+            standing("BOS", Some("Eastern"), "Atlantic", 9, 6, 1),
+            standing("NYR", Some("Eastern"), "Metropolitan", 8, 7, 1),
+            standing("DAL", Some("Western"), "Central", 8, 7, 2),
+            standing("EDM", Some("Western"), "Pacific", 7, 8, 1),
+        ])
+    }
+
+    fn standings_content(width: usize, config: &crate::config::Config) -> String {
+        let columns = crate::commands::standings::SortColumn::all();
+        build_content(width, Tab::Standings, &standings_groups(), &None, &HashMap::new(), &HashMap::new(), GroupBy::Division, &columns, None, config)
+    }
+
+    #[test]
+    fn stale_only_past_the_interval_and_grace() {
+        let last_refresh = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let interval = Duration::from_secs(60);
+        let deadline = last_refresh + interval + STALE_GRACE;
+        assert!(!is_stale(last_refresh, deadline - Duration::from_millis(1), interval));
+        assert!(!is_stale(last_refresh, deadline, interval));
+        assert!(is_stale(last_refresh, deadline + Duration::from_millis(1), interval));
+        // A clock that went backwards is not stale
+        assert!(!is_stale(last_refresh, last_refresh - Duration::from_secs(5), interval));
+    }
+
+    #[test]
+    fn clicks_map_to_the_tab_under_them() {
+        let tabs = Tab::all();
+        // "Scores │ Standings │ Settings"
+        assert_eq!(tab_at(&tabs, 0), Some(Tab::Scores));
+        assert_eq!(tab_at(&tabs, 5), Some(Tab::Scores));
+        assert_eq!(tab_at(&tabs, 9), Some(Tab::Standings));
+        assert_eq!(tab_at(&tabs, 17), Some(Tab::Standings));
+        assert_eq!(tab_at(&tabs, 21), Some(Tab::Settings));
+        // Separators and the space past the last tab are no-ops
+        assert_eq!(tab_at(&tabs, 7), None);
+        assert_eq!(tab_at(&tabs, 29), None);
+    }
+
+    fn render_status(error_message: Option<&str>, status_message: Option<&StatusMessage>, unicode: bool) -> Buffer {
+        let backend = ratatui::backend::TestBackend::new(60, 1);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal
+            .draw(|f| {
+                render_status_bar(f, f.area(), None, Duration::from_secs(60), "%H:%M", error_message, status_message, unicode);
+            })
+            .unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn render_status_line(error_message: Option<&str>, status_message: Option<&StatusMessage>) -> String {
+        let buffer = render_status(error_message, status_message, true);
+        (0..60).map(|x| buffer[(x, 0)].symbol()).collect()
+    }
+
+    fn message(text: &str, is_error: bool) -> StatusMessage {
+        StatusMessage {
+            text: text.to_string(),
+            is_error,
+            created: std::time::Instant::now(),
+        }
+    }
+
+    #[test]
+    fn prompt_shows_over_a_fetch_error() {
+        let prompt = message(":stand", false);
+        let line = render_status_line(Some("Rate limited by the NHL API"), Some(&prompt));
+        assert!(line.contains(":stand"), "{}", line);
+        assert!(!line.contains("ERROR"), "{}", line);
+
+        // Without a message the error still shows
+        let line = render_status_line(Some("Rate limited by the NHL API"), None);
+        assert!(line.starts_with("ERROR: Rate limited"), "{}", line);
+    }
+
+    #[test]
+    fn messages_get_a_prefix_and_color() {
+        let buffer = render_status(None, Some(&message("Failed to copy", true)), true);
+        assert_eq!(buffer[(0, 0)].symbol(), "✖");
+        assert_eq!(buffer[(2, 0)].symbol(), "F");
+        assert_eq!(buffer[(2, 0)].fg, Color::Red);
+        assert!(buffer[(2, 0)].modifier.contains(Modifier::BOLD));
+
+        let buffer = render_status(None, Some(&message("Sorted by W", false)), true);
+        assert_eq!(buffer[(0, 0)].symbol(), "ℹ");
+        assert_eq!(buffer[(2, 0)].fg, Color::Blue);
+    }
+
+    #[test]
+    fn message_prefixes_fall_back_to_ascii() {
+        let buffer = render_status(None, Some(&message("Failed to copy", true)), false);
+        assert_eq!(buffer[(0, 0)].symbol(), "!");
+        let buffer = render_status(None, Some(&message("Sorted by W", false)), false);
+        assert_eq!(buffer[(0, 0)].symbol(), "i");
+        assert_eq!(buffer[(2, 0)].symbol(), "S");
+    }
+
+    #[test]
+    fn standings_stack_below_the_two_column_width() {
+        let config = crate::config::Config::default();
+        let indent = config.content_indent;
+        let headings = |width: usize| {
+            standings_content(width, &config).lines().find(|line| line.contains("Atlantic")).unwrap().trim().to_string()
+        };
+
+        assert_eq!(headings(95 + indent), "Atlantic");
+        assert_eq!(headings(96 + indent).split_whitespace().collect::<Vec<_>>(), ["Atlantic", "Central"]);
+        // Side by side fits exactly in the threshold width
+        let content = standings_content(96 + indent, &config);
+        assert!(content.lines().all(|line| line.width() <= 96 + indent));
+    }
+
+    fn render_tabs(focused: bool, focus_color: Option<Color>) -> Buffer {
+        let backend = ratatui::backend::TestBackend::new(40, 2);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal
+            .draw(|f| render_tab_bar(f, f.area(), &Tab::all(), Tab::Standings, focused, focus_color))
+            .unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    #[test]
+    fn tab_bar_uses_the_focus_color_or_dims() {
+        // "Scores │ Standings │ Settings": Scores at 0, Standings (selected) at 9
+        let buffer = render_tabs(true, Some(Color::LightBlue));
+        assert_eq!(buffer[(0, 0)].fg, Color::LightBlue);
+        assert_eq!(buffer[(0, 1)].fg, Color::LightBlue);
+        assert_eq!(buffer[(9, 0)].fg, Color::LightBlue);
+        assert!(buffer[(9, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(!buffer[(0, 0)].modifier.contains(Modifier::REVERSED));
+
+        // focus_color = "none" keeps the terminal's color
+        let buffer = render_tabs(true, None);
+        assert_eq!(buffer[(0, 0)].fg, Color::Reset);
+
+        let buffer = render_tabs(false, Some(Color::LightBlue));
+        assert_eq!(buffer[(0, 0)].fg, Color::DarkGray);
+        assert_eq!(buffer[(0, 1)].fg, Color::DarkGray);
+        assert_eq!(buffer[(9, 0)].fg, Color::DarkGray);
+        assert!(buffer[(9, 0)].modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn content_indent_shifts_every_line() {
+        let flush = crate::config::Config { content_indent: 0, ..crate::config::Config::default() };
+        let indented = crate::config::Config { content_indent: 4, ..crate::config::Config::default() };
+        // Same room for the standings in both, so only the margin differs
+        let flush_content = standings_content(120, &flush);
+        let indented_content = standings_content(124, &indented);
+
+        assert_eq!(flush_content.lines().count(), indented_content.lines().count());
+        for (flush_line, indented_line) in flush_content.lines().zip(indented_content.lines()) {
+            assert_eq!(indented_line, format!("    {}", flush_line));
+        }
+    }
+}